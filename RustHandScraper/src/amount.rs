@@ -0,0 +1,25 @@
+use crate::money::Money;
+
+// Amounts look like "$0.05", "€1,250.50", "£2", "₹10" or, for play money, a bare "1500".
+// The single capture group holds the number without the currency symbol
+pub const AMOUNT: &str = r"[$€£₹]?([0-9][0-9,]*(?:\.[0-9]+)?)";
+
+// Replaces every "{amount}" in a pattern with the amount lexer
+pub fn expand(pattern: &str) -> String {
+    pattern.replace("{amount}", AMOUNT)
+}
+
+pub fn parse_amount(caps: &regex::Captures<'_>, index: usize) -> Option<Money> {
+    Money::parse(caps.get(index)?.as_str())
+}
+
+// Maps the currency symbol in front of an amount to its ISO code; no symbol means play money
+pub fn currency_from_symbol(symbol: &str) -> &'static str {
+    match symbol {
+        "$" => "USD",
+        "€" => "EUR",
+        "£" => "GBP",
+        "₹" => "INR",
+        _ => "PLAY",
+    }
+}
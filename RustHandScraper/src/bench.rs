@@ -0,0 +1,196 @@
+use std::error::Error;
+use std::fs;
+use std::time::Instant;
+
+use crate::money::Money;
+use crate::{get_hands_from_file, Hand, ParseMode};
+
+// Representative cash hands (fold on the turn, all-in showdown with a side pot, adversarial screen names);
+// {id} is replaced with a unique hand number for every copy written to the synthetic file
+const BENCH_HANDS: [&str; 3] = [
+"PokerStars Hand #{id}:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:22:10 CET [2024/11/03 8:22:10 ET]
+Table 'Aarhus II' 6-max Seat #3 is the button
+Seat 1: PlayerOne ($5.12 in chips)
+Seat 2: Hero ($5 in chips)
+Seat 3: Bob ($4.87 in chips)
+Seat 4: Carl ($2.10 in chips)
+Seat 5: Dave ($6.33 in chips) is sitting out
+Seat 6: Eve ($5 in chips)
+Carl: posts small blind $0.02
+Eve: posts big blind $0.05
+Dave: sits out
+*** HOLE CARDS ***
+Dealt to Hero [Ah Kd]
+PlayerOne: folds
+Hero: raises $0.10 to $0.15
+Bob: calls $0.15
+Carl: folds
+Eve: calls $0.10
+*** FLOP *** [7c 8d 2s]
+Eve: checks
+Hero: bets $0.30
+Bob: folds
+Eve: calls $0.30
+*** TURN *** [7c 8d 2s] [Kh]
+Eve: checks
+Hero: bets $0.75
+Eve: folds
+Uncalled bet ($0.75) returned to Hero
+Hero collected $1.02 from pot
+Hero: doesn't show hand
+*** SUMMARY ***
+Total pot $1.07 | Rake $0.05
+Board [7c 8d 2s Kh]
+Seat 1: PlayerOne folded before Flop (didn't bet)
+Seat 2: Hero collected ($1.02)
+Seat 3: Bob (button) folded on the Flop
+Seat 4: Carl (small blind) folded before Flop
+Seat 5: Dave is sitting out
+Seat 6: Eve (big blind) folded on the Turn",
+"PokerStars Hand #{id}:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:23:40 CET [2024/11/03 8:23:40 ET]
+Table 'Aarhus II' 6-max Seat #4 is the button
+Seat 1: PlayerOne ($5.12 in chips)
+Seat 2: Hero ($6.12 in chips)
+Seat 3: Bob ($4.72 in chips)
+Seat 4: Carl ($2.08 in chips)
+Seat 6: Eve ($4.55 in chips)
+Eve: posts small blind $0.02
+PlayerOne: posts big blind $0.05
+*** HOLE CARDS ***
+Dealt to Hero [Qs Qh]
+Hero: raises $0.10 to $0.15
+Bob: raises $4.57 to $4.72 and is all-in
+Carl: calls $2.08 and is all-in
+Eve: folds
+PlayerOne: folds
+Hero: calls $4.57
+*** FLOP *** [2c 5d 9h]
+*** TURN *** [2c 5d 9h] [Js]
+*** RIVER *** [2c 5d 9h Js] [3c]
+*** SHOW DOWN ***
+Hero: shows [Qs Qh] (a pair of Queens)
+Bob: shows [Ac Kc] (high card Ace)
+Carl: shows [8s 8d] (a pair of Eights)
+Hero collected $5.16 from side pot
+Hero collected $6.12 from main pot
+*** SUMMARY ***
+Total pot $11.59 Main pot $6.31. Side pot $5.28. | Rake $0.31
+Board [2c 5d 9h Js 3c]
+Seat 1: PlayerOne (big blind) folded before Flop
+Seat 2: Hero showed [Qs Qh] and won ($11.28) with a pair of Queens
+Seat 3: Bob showed [Ac Kc] and lost with high card Ace
+Seat 4: Carl (button) showed [8s 8d] and lost with a pair of Eights
+Seat 6: Eve (small blind) folded before Flop",
+"PokerStars Hand #{id}:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:26:00 CET [2024/11/03 8:26:00 ET]
+Table 'Aarhus II' 6-max Seat #1 is the button
+Seat 1: Bob: x ($5 in chips)
+Seat 2: Al collected ($5 in chips)
+Seat 3: Ünïcødé 🂡 ($5 in chips)
+Seat 4: [brackets] (paren) ($5 in chips)
+Seat 5: trailing  ($5 in chips)
+Seat 6: Bob ($5 in chips)
+Al collected: posts small blind $0.02
+Ünïcødé 🂡: posts big blind $0.05
+*** HOLE CARDS ***
+Dealt to Bob [Ah Ad]
+[brackets] (paren): raises $0.10 to $0.15
+trailing : calls $0.15
+Bob is connected
+Bob: raises $0.30 to $0.45
+Bob: x: folds
+Al collected: folds
+Ünïcødé 🂡: folds
+[brackets] (paren): folds
+trailing : raises $4.55 to $5 and is all-in
+Bob: folds
+Uncalled bet ($4.55) returned to trailing 
+trailing  collected $1.12 from pot
+*** SUMMARY ***
+Total pot $1.12 | Rake $0
+Seat 5: trailing  collected ($1.12)",
+];
+
+// The "Total pot" of each of BENCH_HANDS
+const BENCH_POTS: [Money; 3] = [Money::from_cents(107), Money::from_cents(1159), Money::from_cents(112)];
+
+// `hand_count` copies of BENCH_HANDS in the PokerStars layout, each with its own hand number
+fn synthetic_history(hand_count: usize) -> String {
+    (0..hand_count)
+        .map(|index| BENCH_HANDS[index % BENCH_HANDS.len()].replace("{id}", &(250000000000 + index).to_string()))
+        .collect::<Vec<_>>()
+        .join("\n\n\n")
+        .replace("\n", "\r\n")
+}
+
+// Sums over the parsed copies of BENCH_HANDS that must agree exactly when amounts don't drift
+struct Totals {
+    expected_pots: Money,
+    aggregated_pots: Money,
+    chips_before: Money,
+    chips_after_and_rake: Money,
+}
+
+impl Totals {
+    fn new(hands: &[&Hand], hand_count: usize) -> Self {
+        let rake: Money = hands.iter().map(|hand| hand.rake).sum();
+        let chips_after: Money = hands.iter().flat_map(|hand| hand.players.iter()).map(|player| player.chips_after_hand).sum();
+        Totals {
+            expected_pots: (0..hand_count).map(|index| BENCH_POTS[index % BENCH_POTS.len()]).sum(),
+            aggregated_pots: hands.iter().map(|hand| hand.total_pot).sum(),
+            chips_before: hands.iter().flat_map(|hand| hand.players.iter()).map(|player| player.chips).sum(),
+            chips_after_and_rake: chips_after + rake,
+        }
+    }
+
+    fn drifted(&self) -> bool {
+        self.aggregated_pots != self.expected_pots || self.chips_before != self.chips_after_and_rake
+    }
+}
+
+// Writes `hand_count` hands to a temporary history file, parses it with get_hands_from_file
+// and prints the throughput
+pub fn run_parse_benchmark(hand_count: usize) -> Result<(), Box<dyn Error>> {
+    let file_path = std::env::temp_dir().join("ai-poker-coach-parse-bench.txt");
+    fs::write(&file_path, synthetic_history(hand_count))?;
+    println!("Wrote {} synthetic hands to {}", hand_count, file_path.display());
+
+    let start = Instant::now();
+    let results = get_hands_from_file(file_path.to_str().ok_or("Invalid benchmark file path")?, ParseMode::Strict);
+    let elapsed = start.elapsed();
+    fs::remove_file(&file_path).ok();
+
+    let parsed = results.iter().filter(|result| result.is_ok()).count();
+    println!("Parsed {} hands ({} failed) in {:.3}s", parsed, results.len() - parsed, elapsed.as_secs_f64());
+    println!("Throughput: {:.0} hands/second", results.len() as f64 / elapsed.as_secs_f64());
+
+    // Amounts are exact, so aggregating every pot and stack must not drift by a single cent
+    let hands = results.iter().filter_map(|result| result.as_ref().ok()).collect::<Vec<_>>();
+    let totals = Totals::new(&hands, hand_count);
+    println!("Aggregated pots: {:.2} (expected {:.2}), chips before: {:.2}, chips after + rake: {:.2}", totals.aggregated_pots, totals.expected_pots, totals.chips_before, totals.chips_after_and_rake);
+    if totals.drifted() {
+        return Err("Aggregated amounts drifted".into());
+    }
+
+    let invalid = hands.iter().filter(|hand| !hand.validate(None).is_empty()).count();
+    println!("Hands failing validation: {}", invalid);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand_file::{decode, split_hands};
+
+    #[test]
+    fn aggregating_thousands_of_hands_has_no_rounding_error() {
+        let hand_count = 3000;
+        let contents = decode(synthetic_history(hand_count).as_bytes()).unwrap();
+        let hands = split_hands(&contents).into_iter().map(|hand_str| Hand::from_str(hand_str, ParseMode::Strict).unwrap()).collect::<Vec<Hand>>();
+        assert_eq!(hands.len(), hand_count);
+
+        let totals = Totals::new(&hands.iter().collect::<Vec<&Hand>>(), hand_count);
+        assert_eq!(totals.aggregated_pots, totals.expected_pots);
+        assert_eq!(totals.chips_before, totals.chips_after_and_rake);
+        assert!(hands.iter().all(|hand| hand.validate(None).is_empty()));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
@@ -6,12 +7,44 @@ use dirs;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub pokerstars_handhistory_path: Option<String>,
+    // Currency that results are normalised to, e.g. "USD"
+    pub reporting_currency: Option<String>,
+    // How much one unit of the keyed currency is worth in the reporting currency, e.g. {"EUR": 1.08}
+    #[serde(default)]
+    pub currency_rates: HashMap<String, f32>,
+    // Skip uploading hands that fail Hand::validate instead of only reporting them
+    #[serde(default)]
+    pub block_invalid_uploads: bool,
+    // The user's PokerStars screen names, used to find hero in hands without a "Dealt to" line
+    #[serde(default)]
+    pub screen_names: Vec<String>,
+    // Skip uploading hands hero wasn't seated at instead of uploading them flagged as observed
+    #[serde(default)]
+    pub skip_observed_hands: bool,
+}
+
+impl AppConfig {
+    // Returns the reporting currency and the rate to convert `currency` into it,
+    // or None if no reporting currency is set or the rate is unknown (e.g. play money)
+    pub fn reporting_conversion(&self, currency: &str) -> Option<(String, f32)> {
+        let reporting_currency = self.reporting_currency.as_ref()?;
+        if reporting_currency == currency {
+            return Some((reporting_currency.clone(), 1.0));
+        }
+        let rate = self.currency_rates.get(currency)?;
+        Some((reporting_currency.clone(), *rate))
+    }
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             pokerstars_handhistory_path: None,
+            reporting_currency: None,
+            currency_rates: HashMap::new(),
+            block_invalid_uploads: false,
+            screen_names: Vec::new(),
+            skip_observed_hands: false,
         }
     }
 }
@@ -0,0 +1,277 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::amount::{self, parse_amount, currency_from_symbol};
+use crate::money::Money;
+use crate::parse_error::HandParseErrorKind;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameVariant {
+    Holdem,
+    ShortDeckHoldem,
+    Omaha,
+    OmahaHiLo,
+    FiveCardOmaha,
+    SixCardOmaha,
+    Courchevel,
+    Other(String),
+}
+
+impl GameVariant {
+    fn from_str(variant: &str) -> Self {
+        match variant {
+            "Hold'em" => GameVariant::Holdem,
+            "6+ Hold'em" => GameVariant::ShortDeckHoldem,
+            "Omaha" => GameVariant::Omaha,
+            "Omaha Hi/Lo" => GameVariant::OmahaHiLo,
+            "5 Card Omaha" => GameVariant::FiveCardOmaha,
+            "6 Card Omaha" => GameVariant::SixCardOmaha,
+            "Courchevel" => GameVariant::Courchevel,
+            other => GameVariant::Other(other.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            GameVariant::Holdem => "Holdem",
+            GameVariant::ShortDeckHoldem => "ShortDeckHoldem",
+            GameVariant::Omaha => "Omaha",
+            GameVariant::OmahaHiLo => "OmahaHiLo",
+            GameVariant::FiveCardOmaha => "FiveCardOmaha",
+            GameVariant::SixCardOmaha => "SixCardOmaha",
+            GameVariant::Courchevel => "Courchevel",
+            GameVariant::Other(other) => other,
+        }
+    }
+
+    // Variants the hand parser understands end to end
+    pub fn is_supported(&self) -> bool {
+        matches!(self, GameVariant::Holdem | GameVariant::Omaha | GameVariant::FiveCardOmaha | GameVariant::SixCardOmaha)
+    }
+
+    pub fn hole_card_count(&self) -> usize {
+        match self {
+            GameVariant::Holdem | GameVariant::ShortDeckHoldem => 2,
+            GameVariant::Omaha | GameVariant::OmahaHiLo => 4,
+            GameVariant::FiveCardOmaha | GameVariant::Courchevel => 5,
+            GameVariant::SixCardOmaha => 6,
+            GameVariant::Other(_) => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitType {
+    No,
+    Pot,
+    Fixed,
+}
+
+impl LimitType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LimitType::No => "NoLimit",
+            LimitType::Pot => "PotLimit",
+            LimitType::Fixed => "FixedLimit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableKind {
+    HeadsUp,
+    SixMax,
+    FullRing,
+}
+
+impl TableKind {
+    fn from_max_players(max_players: u32) -> Self {
+        match max_players {
+            0..=2 => TableKind::HeadsUp,
+            3..=6 => TableKind::SixMax,
+            _ => TableKind::FullRing,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TableKind::HeadsUp => "HeadsUp",
+            TableKind::SixMax => "SixMax",
+            TableKind::FullRing => "FullRing",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GameInfo {
+    pub variant: GameVariant,
+    pub limit: LimitType,
+    pub currency: String,
+    pub small_blind: Money,
+    pub big_blind: Money,
+    pub is_zoom: bool,
+    pub is_fast_fold: bool,
+    pub table_kind: TableKind,
+    pub pool: Option<String>, // Zoom hands move tables every hand; the pool is the game, stakes and table size they share
+}
+
+impl GameInfo {
+    // Parses e.g. "PokerStars Hand #250123456789:  Hold'em No Limit ($0.02/$0.05 USD) - ..."
+    pub fn from_header(header: &str, max_players: u32) -> Result<Self, HandParseErrorKind> {
+        static GAME_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"^PokerStars (Zoom )?Hand #\d+:\s+(Tournament #\d+, (?:Freeroll|[$€£₹]?[0-9.,]+(?:\+[$€£₹]?[0-9.,]+)*)(?: [A-Z]{3})?\s+)?(.+?) (No Limit|Pot Limit|Fixed Limit|Limit)(?: - (?:Match Round [IVXLCDM]+, )?Level [IVXLCDM]+)? \(([$€£₹]?){amount}/{amount}(?: ([A-Z]{3}))?\)")).unwrap());
+
+        let caps = GAME_RE.captures(header).ok_or(HandParseErrorKind::MissingHeader)?;
+
+        let variant = GameVariant::from_str(caps.get(3).unwrap().as_str());
+        if !variant.is_supported() {
+            return Err(HandParseErrorKind::UnsupportedGame(caps.get(3).unwrap().as_str().to_string()));
+        }
+
+        let limit = match caps.get(4).unwrap().as_str() {
+            "No Limit" => LimitType::No,
+            "Pot Limit" => LimitType::Pot,
+            _ => LimitType::Fixed,
+        };
+
+        // Tournament amounts are chips; for cash games prefer the ISO code after the stakes
+        // (CAD is written with "$"), falling back to the symbol in front of them
+        let currency = match (caps.get(2), caps.get(8)) {
+            (Some(_), _) => "CHIPS".to_string(),
+            (None, Some(code)) => code.as_str().to_string(),
+            (None, None) => currency_from_symbol(caps.get(5).unwrap().as_str()).to_string(),
+        };
+
+        let small_blind = parse_amount(&caps, 6).ok_or(HandParseErrorKind::InvalidNumber)?;
+        let big_blind = parse_amount(&caps, 7).ok_or(HandParseErrorKind::InvalidNumber)?;
+        let is_zoom = caps.get(1).is_some();
+        let table_kind = TableKind::from_max_players(max_players);
+        let pool = is_zoom.then(|| format!("{} {} {}/{} {} {}", variant.as_str(), limit.as_str(), small_blind, big_blind, currency, table_kind.as_str()));
+
+        Ok(GameInfo {
+            variant,
+            limit,
+            currency,
+            small_blind,
+            big_blind,
+            is_zoom,
+            is_fast_fold: is_zoom, // Zoom is PokerStars' only fast-fold format
+            table_kind,
+            pool,
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"variant\":\"{}\",\"limit\":\"{}\",\"currency\":\"{}\",\"small_blind\":{},\"big_blind\":{},\"is_zoom\":{},\"is_fast_fold\":{},\"table_kind\":\"{}\",\"pool\":{}}}",
+            self.variant.as_str().replace("\"", "\\\""),
+            self.limit.as_str(),
+            self.currency,
+            self.small_blind,
+            self.big_blind,
+            self.is_zoom,
+            self.is_fast_fold,
+            self.table_kind.as_str(),
+            self.pool.as_ref().map_or("null".to_string(), |pool| format!("\"{}\"", pool.replace("\"", "\\\"")))
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TournamentInfo {
+    pub id: String,
+    pub buy_in: Money, // the part of the buy-in that goes to the prize pool
+    pub bounty: Money,
+    pub fee: Money,
+    pub currency: String,
+    pub level: u32,
+}
+
+#[derive(Debug, Clone)]
+pub enum HandKind {
+    Cash,
+    Tournament(TournamentInfo),
+}
+
+impl HandKind {
+    // Parses e.g. "PokerStars Hand #250123456789: Tournament #3456789012, $0.98+$0.12 USD Hold'em No Limit - Level IV (25/50) - ..."
+    pub fn from_header(header: &str) -> Result<Self, HandParseErrorKind> {
+        static TOURNAMENT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^PokerStars Hand #\d+:\s+Tournament #(\d+), (Freeroll|[$€£₹]?[0-9.,]+(?:\+[$€£₹]?[0-9.,]+)*)(?: ([A-Z]{3}))?\s+.+? - (?:Match Round [IVXLCDM]+, )?Level ([IVXLCDM]+) ").unwrap());
+
+        if !header.contains(" Tournament #") {
+            return Ok(HandKind::Cash);
+        }
+        let caps = TOURNAMENT_RE.captures(header).ok_or(HandParseErrorKind::MissingHeader)?;
+
+        let buy_in_str = caps.get(2).unwrap().as_str();
+        let parts = match buy_in_str {
+            "Freeroll" => vec![],
+            _ => buy_in_str.split('+')
+                .map(|part| Money::parse(part.trim_start_matches(['$', '€', '£', '₹'])).ok_or(HandParseErrorKind::InvalidNumber))
+                .collect::<Result<Vec<Money>, _>>()?,
+        };
+        // "buy-in+fee" or, for knockouts, "buy-in+bounty+fee"
+        let (buy_in, bounty, fee) = match parts.as_slice() {
+            [] => (Money::ZERO, Money::ZERO, Money::ZERO),
+            [buy_in] => (*buy_in, Money::ZERO, Money::ZERO),
+            [buy_in, fee] => (*buy_in, Money::ZERO, *fee),
+            [buy_in, bounty, fee, ..] => (*buy_in, *bounty, *fee),
+        };
+
+        let currency = match caps.get(3) {
+            Some(code) => code.as_str().to_string(),
+            None if buy_in_str == "Freeroll" => "".to_string(),
+            None => currency_from_symbol(buy_in_str.split(|c: char| c.is_ascii_digit()).next().unwrap_or("")).to_string(),
+        };
+
+        Ok(HandKind::Tournament(TournamentInfo {
+            id: caps.get(1).unwrap().as_str().to_string(),
+            buy_in,
+            bounty,
+            fee,
+            currency,
+            level: parse_roman(caps.get(4).unwrap().as_str()),
+        }))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            HandKind::Cash => "Cash",
+            HandKind::Tournament(_) => "Tournament",
+        }
+    }
+
+    // The tournament section of the upload JSON, null for cash hands
+    pub fn to_json(&self) -> String {
+        match self {
+            HandKind::Cash => "null".to_string(),
+            HandKind::Tournament(info) => format!(
+                "{{\"id\":\"{}\",\"buy_in\":{},\"bounty\":{},\"fee\":{},\"currency\":\"{}\",\"level\":{}}}",
+                info.id,
+                info.buy_in,
+                info.bounty,
+                info.fee,
+                info.currency,
+                info.level
+            ),
+        }
+    }
+}
+
+// Blind levels are written in Roman numerals
+fn parse_roman(numeral: &str) -> u32 {
+    let values = numeral.chars().map(|c| match c {
+        'I' => 1,
+        'V' => 5,
+        'X' => 10,
+        'L' => 50,
+        'C' => 100,
+        'D' => 500,
+        'M' => 1000,
+        _ => 0,
+    }).collect::<Vec<i32>>();
+    let total = values.iter().enumerate().fold(0, |total, (index, &value)| {
+        if values.get(index + 1).is_some_and(|&next| next > value) { total - value } else { total + value }
+    });
+    total.max(0) as u32
+}
@@ -0,0 +1,104 @@
+use std::fs;
+use std::io;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+// Reads a hand history file whatever PokerStars or the copy to another machine did to it: UTF-8 with or without
+// a BOM, UTF-16 (LE or BE) exports, and CRLF or LF line endings. Line endings are normalised to "\n"
+pub fn read_hand_file(file_path: &str) -> io::Result<String> {
+    let bytes = fs::read(file_path)?;
+    decode(&bytes).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{} is not UTF-8 or UTF-16 text", file_path)))
+}
+
+pub fn decode(bytes: &[u8]) -> Option<String> {
+    let text = if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8(rest.to_vec()).ok()?
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        decode_utf16(rest, u16::from_le_bytes)?
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        decode_utf16(rest, u16::from_be_bytes)?
+    } else if let Some(from_bytes) = utf16_without_bom(bytes) {
+        decode_utf16(bytes, from_bytes)?
+    } else {
+        String::from_utf8(bytes.to_vec()).ok()?
+    };
+    Some(text.replace("\r\n", "\n").replace('\r', "\n"))
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Option<String> {
+    let units = bytes.chunks_exact(2).map(|pair| from_bytes([pair[0], pair[1]])).collect::<Vec<u16>>();
+    String::from_utf16(&units).ok()
+}
+
+// Some installs write UTF-16 without a BOM; the history is mostly ASCII, so every other byte is zero,
+// the second of each pair for little endian and the first for big endian
+fn utf16_without_bom(bytes: &[u8]) -> Option<fn([u8; 2]) -> u16> {
+    let sample = &bytes[..bytes.len().min(512) & !1];
+    let mostly = |is_ascii: fn(&[u8]) -> bool| !sample.is_empty() && sample.chunks_exact(2).filter(|pair| is_ascii(pair)).count() * 10 >= sample.len() / 2 * 9;
+    if mostly(|pair| pair[1] == 0 && pair[0] != 0) {
+        Some(u16::from_le_bytes)
+    } else if mostly(|pair| pair[0] == 0 && pair[1] != 0) {
+        Some(u16::from_be_bytes)
+    } else {
+        None
+    }
+}
+
+// Splits a file into hands at every header line, so it doesn't matter how many blank lines separate them.
+// Localized clients name the hand differently ("Main n°", "Mano n.º", "Mão n.º")
+pub fn split_hands(contents: &str) -> Vec<&str> {
+    static HEADER_START_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^PokerStars (?:Zoom )?(?:Hand #|Main n°|Mano n\.|Mão n\.º)").unwrap());
+
+    let starts = HEADER_START_RE.find_iter(contents).map(|header| header.start()).collect::<Vec<usize>>();
+    starts.iter()
+        .enumerate()
+        .map(|(index, &start)| contents[start..starts.get(index + 1).copied().unwrap_or(contents.len())].trim_end())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HISTORY: &str = "PokerStars Hand #1:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:22:10 CET\nSeat 1: Zoë (€5 in chips)\n\n\nPokerStars Zoom Hand #2:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:23:40 CET\nSeat 1: Zoë (€5 in chips)\n";
+
+    fn utf16(text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+        text.encode_utf16().flat_map(to_bytes).collect()
+    }
+
+    #[test]
+    fn decodes_every_encoding_and_line_ending() {
+        for line_ending in ["\n", "\r\n"] {
+            let text = HISTORY.replace('\n', line_ending);
+            let encodings: [(&str, Vec<u8>); 6] = [
+                ("UTF-8", text.as_bytes().to_vec()),
+                ("UTF-8 with BOM", [&[0xEF, 0xBB, 0xBF], text.as_bytes()].concat()),
+                ("UTF-16LE with BOM", [vec![0xFF, 0xFE], utf16(&text, u16::to_le_bytes)].concat()),
+                ("UTF-16BE with BOM", [vec![0xFE, 0xFF], utf16(&text, u16::to_be_bytes)].concat()),
+                ("UTF-16LE", utf16(&text, u16::to_le_bytes)),
+                ("UTF-16BE", utf16(&text, u16::to_be_bytes)),
+            ];
+            for (encoding, bytes) in encodings {
+                assert_eq!(decode(&bytes).as_deref(), Some(HISTORY), "{} with {:?} line endings", encoding, line_ending);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_bytes_that_are_not_text() {
+        assert_eq!(decode(&[0x50, 0xFF, 0xC3, 0x28, 0x80]), None);
+    }
+
+    #[test]
+    fn splits_on_header_lines_whatever_separates_the_hands() {
+        let hands = split_hands(HISTORY);
+        assert_eq!(hands.len(), 2);
+        assert!(hands[0].starts_with("PokerStars Hand #1:") && hands[0].ends_with("(€5 in chips)"));
+        assert!(hands[1].starts_with("PokerStars Zoom Hand #2:") && hands[1].ends_with("(€5 in chips)"));
+
+        let localized = "PokerStars Main n°3 :  Hold'em No Limit\nSeat 1\nPokerStars Mano n.º 4:  Hold'em No Limit\nPokerStars Mão n.º 5:  Hold'em No Limit\n";
+        assert_eq!(split_hands(localized).len(), 3);
+        assert!(split_hands("\n\n").is_empty());
+    }
+}
@@ -0,0 +1,781 @@
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+    Portuguese,
+    Italian,
+}
+
+// The words a client language uses in the hand history. Everything is translated to the English layout the
+// grammar in main.rs parses, so a new language only needs a table here.
+// Phrases are regexes applied line by line in order (amounts are already "€1.50" style by then, so `(\S+)`
+// matches one); they also cover the summary's seat outcomes. Action verbs are what follows "name: " for a
+// seated name and are matched longest first
+struct Keywords {
+    markers: &'static [&'static str], // words in the header or table line that no other language uses
+    phrases: &'static [(&'static str, &'static str)],
+    actions: &'static [(&'static str, &'static str)],
+    raise_to: &'static str, // "to" in "raises $0.10 to $0.15"
+    all_in: &'static str, // "and is all-in"
+    time_zones: &'static [(&'static str, &'static str)],
+    day_first_dates: bool, // dates written "03.11.2024" or "03/11/2024" instead of "2024/11/03"
+    decimal_comma: bool, // "0,05 €" instead of "€0.05"
+}
+
+static GERMAN: Keywords = Keywords {
+    markers: &["Tisch '", " Platz #"],
+    phrases: &[
+        (r"^Tisch '", "Table '"),
+        (r" Platz #(\d+) ist der Button", " Seat #$1 is the button"),
+        (r"^Platz (\d+): ", "Seat $1: "),
+        (r" in Chips\)", " in chips)"),
+        (r" ist nicht aktiv$", " is sitting out"),
+        (r"^\*\*\* SHOWDOWN \*\*\*", "*** SHOW DOWN ***"),
+        (r"^\*\*\* ZUSAMMENFASSUNG \*\*\*", "*** SUMMARY ***"),
+        (r"^Ausgeteilt an ", "Dealt to "),
+        (r"^Nicht gecallter Einsatz \((\S+)\) an (.+) zurückgegeben$", "Uncalled bet ($1) returned to $2"),
+        (r"^(.+) hat (\S+) aus dem Hauptpot gewonnen$", "$1 collected $2 from main pot"),
+        (r"^(.+) hat (\S+) aus dem Nebenpot gewonnen$", "$1 collected $2 from side pot"),
+        (r"^(.+) hat (\S+) aus dem Pot gewonnen$", "$1 collected $2 from pot"),
+        (r"^Gesamter Pot (\S+)", "Total pot $1"),
+        (r" \(Button\)", " (button)"),
+        (r" \(Small Blind\)", " (small blind)"),
+        (r" \(Big Blind\)", " (big blind)"),
+        (r" passte vor dem Flop", " folded before Flop"),
+        (r" passte (?:im|am) (Flop|Turn|River)", " folded on the $1"),
+        (r" \(hat nicht gesetzt\)", " (didn't bet)"),
+        (r" zeigte \[", " showed ["),
+        (r"\] und gewann \(", "] and won ("),
+        (r"\] und verlor mit ", "] and lost with "),
+        (r"\) mit ", ") with "),
+        (r" verdeckte \[", " mucked ["),
+        (r" verdeckte$", " mucked"),
+        (r" kassierte \((\S+)\)$", " collected ($1)"),
+    ],
+    actions: &[
+        ("postet Small Blind", "posts small blind"),
+        ("postet Big Blind", "posts big blind"),
+        ("postet Ante", "posts the ante"),
+        ("postet Small & Big Blind", "posts small & big blinds"),
+        ("postet Straddle", "posts straddle"),
+        ("zeigt das Blatt nicht", "doesn't show hand"),
+        ("wirft das Blatt weg", "mucks hand"),
+        ("passt", "folds"),
+        ("checkt", "checks"),
+        ("callt", "calls"),
+        ("erhöht", "raises"),
+        ("setzt", "bets"),
+        ("zeigt", "shows"),
+    ],
+    raise_to: " auf ",
+    all_in: " und ist all-in",
+    time_zones: &[("MEZ", "CET"), ("MESZ", "CET")],
+    day_first_dates: true,
+    decimal_comma: true,
+};
+
+static FRENCH: Keywords = Keywords {
+    markers: &["Main n°", "Siège n°"],
+    phrases: &[
+        (r"^PokerStars (Zoom )?Main n°", "PokerStars ${1}Hand #"),
+        (r" Siège n°(\d+) est le bouton", " Seat #$1 is the button"),
+        (r"^Siège (\d+) ?: ", "Seat $1: "),
+        (r" en jetons\)", " in chips)"),
+        (r" est absent$", " is sitting out"),
+        (r"^\*\*\* CARTES FERMÉES \*\*\*", "*** HOLE CARDS ***"),
+        (r"^\*\*\* TOURNANT \*\*\*", "*** TURN ***"),
+        (r"^\*\*\* RIVIÈRE \*\*\*", "*** RIVER ***"),
+        (r"^\*\*\* ABATTAGE \*\*\*", "*** SHOW DOWN ***"),
+        (r"^\*\*\* RÉSUMÉ \*\*\*", "*** SUMMARY ***"),
+        (r"^Distribuées à ", "Dealt to "),
+        (r"^Mise non suivie \((\S+)\) retournée à ", "Uncalled bet ($1) returned to "),
+        (r"^(.+) a remporté (\S+) du pot principal$", "$1 collected $2 from main pot"),
+        (r"^(.+) a remporté (\S+) du pot annexe$", "$1 collected $2 from side pot"),
+        (r"^(.+) a remporté (\S+) du pot$", "$1 collected $2 from pot"),
+        (r"^Pot total (\S+)", "Total pot $1"),
+        (r" \| Commission ", " | Rake "),
+        (r"^Tableau \[", "Board ["),
+        (r" \(bouton\)", " (button)"),
+        (r" \(petite blind\)", " (small blind)"),
+        (r" \(grosse blind\)", " (big blind)"),
+        (r" s'est couché avant le Flop", " folded before Flop"),
+        (r" s'est couché au Flop", " folded on the Flop"),
+        (r" s'est couché au Tournant", " folded on the Turn"),
+        (r" s'est couché à la Rivière", " folded on the River"),
+        (r" \(n'a pas misé\)", " (didn't bet)"),
+        (r" a montré \[", " showed ["),
+        (r"\] et a gagné \(", "] and won ("),
+        (r"\] et a perdu avec ", "] and lost with "),
+        (r"\) avec ", ") with "),
+        (r" a jeté \[", " mucked ["),
+        (r" a jeté$", " mucked"),
+        (r" a remporté \((\S+)\)$", " collected ($1)"),
+    ],
+    actions: &[
+        ("poste la petite blind", "posts small blind"),
+        ("poste la grosse blind", "posts big blind"),
+        ("poste l'ante", "posts the ante"),
+        ("poste la petite et la grosse blind", "posts small & big blinds"),
+        ("poste un straddle", "posts straddle"),
+        ("ne montre pas", "doesn't show hand"),
+        ("jette la main", "mucks hand"),
+        ("passe", "folds"),
+        ("checke", "checks"),
+        ("suit", "calls"),
+        ("relance", "raises"),
+        ("mise", "bets"),
+        ("montre", "shows"),
+    ],
+    raise_to: " à ",
+    all_in: " et est à tapis",
+    time_zones: &[("HEC", "CET")],
+    day_first_dates: true,
+    decimal_comma: true,
+};
+
+static SPANISH: Keywords = Keywords {
+    markers: &["Mano n.º", " Asiento #"],
+    phrases: &[
+        (r"^PokerStars (Zoom )?Mano n\.º", "PokerStars ${1}Hand #"),
+        (r"^Mesa '", "Table '"),
+        (r" Asiento #(\d+) es el botón", " Seat #$1 is the button"),
+        (r"^Asiento (\d+): ", "Seat $1: "),
+        (r" en fichas\)", " in chips)"),
+        (r" está ausente$", " is sitting out"),
+        (r"^\*\*\* CARTAS DE MANO \*\*\*", "*** HOLE CARDS ***"),
+        (r"^\*\*\* ENFRENTAMIENTO \*\*\*", "*** SHOW DOWN ***"),
+        (r"^\*\*\* RESUMEN \*\*\*", "*** SUMMARY ***"),
+        (r"^Repartidas a ", "Dealt to "),
+        (r"^La apuesta no igualada \((\S+)\) ha sido devuelta a ", "Uncalled bet ($1) returned to "),
+        (r"^(.+) se llevó (\S+) del bote principal$", "$1 collected $2 from main pot"),
+        (r"^(.+) se llevó (\S+) del bote secundario$", "$1 collected $2 from side pot"),
+        (r"^(.+) se llevó (\S+) del bote$", "$1 collected $2 from pot"),
+        (r"^Bote total (\S+)", "Total pot $1"),
+        (r" \| Comisión ", " | Rake "),
+        (r"^Mesa \[", "Board ["),
+        (r" \(botón\)", " (button)"),
+        (r" \(ciega pequeña\)", " (small blind)"),
+        (r" \(ciega grande\)", " (big blind)"),
+        (r" se retiró antes del Flop", " folded before Flop"),
+        (r" se retiró en el (Flop|Turn|River)", " folded on the $1"),
+        (r" \(no apostó\)", " (didn't bet)"),
+        (r" mostró \[", " showed ["),
+        (r"\] y ganó \(", "] and won ("),
+        (r"\] y perdió con ", "] and lost with "),
+        (r"\) con ", ") with "),
+        (r" descartó \[", " mucked ["),
+        (r" descartó$", " mucked"),
+        (r" se llevó \((\S+)\)$", " collected ($1)"),
+    ],
+    actions: &[
+        ("pone la ciega pequeña", "posts small blind"),
+        ("pone la ciega grande", "posts big blind"),
+        ("pone el ante", "posts the ante"),
+        ("pone las ciegas pequeña y grande", "posts small & big blinds"),
+        ("pone un straddle", "posts straddle"),
+        ("no muestra la mano", "doesn't show hand"),
+        ("descarta la mano", "mucks hand"),
+        ("se retira", "folds"),
+        ("pasa", "checks"),
+        ("iguala", "calls"),
+        ("sube", "raises"),
+        ("apuesta", "bets"),
+        ("muestra", "shows"),
+    ],
+    raise_to: " a ",
+    all_in: " y va all-in",
+    time_zones: &[],
+    day_first_dates: false,
+    decimal_comma: true,
+};
+
+static PORTUGUESE: Keywords = Keywords {
+    markers: &["Mão n.º", " Lugar #"],
+    phrases: &[
+        (r"^PokerStars (Zoom )?Mão n\.º", "PokerStars ${1}Hand #"),
+        (r"^Mesa '", "Table '"),
+        (r" Lugar #(\d+) é o botão", " Seat #$1 is the button"),
+        (r"^Lugar (\d+): ", "Seat $1: "),
+        (r" em fichas\)", " in chips)"),
+        (r" está ausente$", " is sitting out"),
+        (r"^\*\*\* CARTAS DA MÃO \*\*\*", "*** HOLE CARDS ***"),
+        (r"^\*\*\* CONFRONTO \*\*\*", "*** SHOW DOWN ***"),
+        (r"^\*\*\* RESUMO \*\*\*", "*** SUMMARY ***"),
+        (r"^Cartas de ", "Dealt to "),
+        (r"^Aposta não igualada \((\S+)\) devolvida a ", "Uncalled bet ($1) returned to "),
+        (r"^(.+) recebeu (\S+) do pote principal$", "$1 collected $2 from main pot"),
+        (r"^(.+) recebeu (\S+) do pote paralelo$", "$1 collected $2 from side pot"),
+        (r"^(.+) recebeu (\S+) do pote$", "$1 collected $2 from pot"),
+        (r"^Pote total (\S+)", "Total pot $1"),
+        (r" \| Taxa ", " | Rake "),
+        (r"^Bordo \[", "Board ["),
+        (r" \(botão\)", " (button)"),
+        (r" desistiu antes do Flop", " folded before Flop"),
+        (r" desistiu no (Flop|Turn|River)", " folded on the $1"),
+        (r" \(não apostou\)", " (didn't bet)"),
+        (r" mostrou \[", " showed ["),
+        (r"\] e ganhou \(", "] and won ("),
+        (r"\] e perdeu com ", "] and lost with "),
+        (r"\) com ", ") with "),
+        (r" descartou \[", " mucked ["),
+        (r" descartou$", " mucked"),
+        (r" recebeu \((\S+)\)$", " collected ($1)"),
+    ],
+    actions: &[
+        ("paga o small blind", "posts small blind"),
+        ("paga o big blind", "posts big blind"),
+        ("paga o ante", "posts the ante"),
+        ("paga o small e o big blind", "posts small & big blinds"),
+        ("paga o straddle", "posts straddle"),
+        ("não mostra a mão", "doesn't show hand"),
+        ("descarta a mão", "mucks hand"),
+        ("desiste", "folds"),
+        ("passa", "checks"),
+        ("iguala", "calls"),
+        ("aumenta", "raises"),
+        ("aposta", "bets"),
+        ("mostra", "shows"),
+    ],
+    raise_to: " para ",
+    all_in: " e está all-in",
+    time_zones: &[],
+    day_first_dates: false,
+    decimal_comma: true,
+};
+
+static ITALIAN: Keywords = Keywords {
+    markers: &["Tavolo '", " Posto #"],
+    phrases: &[
+        (r"^PokerStars (Zoom )?Mano n\.", "PokerStars ${1}Hand #"),
+        (r"^Tavolo '", "Table '"),
+        (r" Posto #(\d+) è il bottone", " Seat #$1 is the button"),
+        (r"^Posto (\d+): ", "Seat $1: "),
+        (r" in fiches\)", " in chips)"),
+        (r" è seduto fuori$", " is sitting out"),
+        (r"^\*\*\* CARTE COPERTE \*\*\*", "*** HOLE CARDS ***"),
+        (r"^\*\*\* SHOWDOWN \*\*\*", "*** SHOW DOWN ***"),
+        (r"^\*\*\* RIEPILOGO \*\*\*", "*** SUMMARY ***"),
+        (r"^Carte distribuite a ", "Dealt to "),
+        (r"^Puntata non chiamata \((\S+)\) restituita a ", "Uncalled bet ($1) returned to "),
+        (r"^(.+) ha incassato (\S+) dal piatto principale$", "$1 collected $2 from main pot"),
+        (r"^(.+) ha incassato (\S+) dal piatto laterale$", "$1 collected $2 from side pot"),
+        (r"^(.+) ha incassato (\S+) dal piatto$", "$1 collected $2 from pot"),
+        (r"^Piatto totale (\S+)", "Total pot $1"),
+        (r" \(bottone\)", " (button)"),
+        (r" ha passato prima del Flop", " folded before Flop"),
+        (r" ha passato al (Flop|Turn|River)", " folded on the $1"),
+        (r" \(non ha puntato\)", " (didn't bet)"),
+        (r" ha mostrato \[", " showed ["),
+        (r"\] e ha vinto \(", "] and won ("),
+        (r"\] e ha perso con ", "] and lost with "),
+        (r"\) con ", ") with "),
+        (r" ha scartato \[", " mucked ["),
+        (r" ha scartato$", " mucked"),
+        (r" ha incassato \((\S+)\)$", " collected ($1)"),
+    ],
+    actions: &[
+        ("paga lo small blind", "posts small blind"),
+        ("paga il big blind", "posts big blind"),
+        ("paga l'ante", "posts the ante"),
+        ("paga lo small e il big blind", "posts small & big blinds"),
+        ("paga lo straddle", "posts straddle"),
+        ("non mostra la mano", "doesn't show hand"),
+        ("scarta la mano", "mucks hand"),
+        ("passa", "folds"),
+        ("fa check", "checks"),
+        ("chiama", "calls"),
+        ("rilancia", "raises"),
+        ("punta", "bets"),
+        ("mostra", "shows"),
+    ],
+    raise_to: " a ",
+    all_in: " ed è all-in",
+    time_zones: &[],
+    day_first_dates: true,
+    decimal_comma: true,
+};
+
+// Compiled tables, built the first time a hand in that language is seen
+struct Grammar {
+    keywords: &'static Keywords,
+    phrases: Vec<(Regex, &'static str)>,
+    verb: Regex,
+}
+
+impl Grammar {
+    fn new(keywords: &'static Keywords) -> Self {
+        let mut verbs = keywords.actions.iter().map(|(verb, _)| regex::escape(verb)).collect::<Vec<_>>();
+        verbs.sort_by_key(|verb| std::cmp::Reverse(verb.len()));
+        Grammar {
+            keywords,
+            phrases: keywords.phrases.iter().map(|(pattern, english)| (Regex::new(pattern).unwrap(), *english)).collect(),
+            // What follows a seated name on an action line
+            verb: Regex::new(&format!(r"^ ?: ({})(\b.*)$", verbs.join("|"))).unwrap(),
+        }
+    }
+}
+
+impl Language {
+    // Reads the language from the header and table lines; anything we don't recognise is parsed as English.
+    // Markers are unique to their language, so the order languages are tried in doesn't matter
+    pub fn detect(hand_str: &str) -> Language {
+        let head = hand_str.lines().take(2).collect::<Vec<_>>().join("\n");
+        [Language::French, Language::Spanish, Language::Portuguese, Language::Italian, Language::German]
+            .into_iter()
+            .find(|language| language.keywords().is_some_and(|keywords| keywords.markers.iter().any(|marker| head.contains(marker))))
+            .unwrap_or(Language::English)
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Language::English => "English",
+            Language::German => "German",
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+            Language::Portuguese => "Portuguese",
+            Language::Italian => "Italian",
+        }
+    }
+
+    fn keywords(&self) -> Option<&'static Keywords> {
+        match self {
+            Language::English => None,
+            Language::German => Some(&GERMAN),
+            Language::French => Some(&FRENCH),
+            Language::Spanish => Some(&SPANISH),
+            Language::Portuguese => Some(&PORTUGUESE),
+            Language::Italian => Some(&ITALIAN),
+        }
+    }
+
+    fn grammar(&self) -> Option<&'static Grammar> {
+        static GERMAN_GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| Grammar::new(&GERMAN));
+        static FRENCH_GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| Grammar::new(&FRENCH));
+        static SPANISH_GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| Grammar::new(&SPANISH));
+        static PORTUGUESE_GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| Grammar::new(&PORTUGUESE));
+        static ITALIAN_GRAMMAR: LazyLock<Grammar> = LazyLock::new(|| Grammar::new(&ITALIAN));
+        match self {
+            Language::English => None,
+            Language::German => Some(&GERMAN_GRAMMAR),
+            Language::French => Some(&FRENCH_GRAMMAR),
+            Language::Spanish => Some(&SPANISH_GRAMMAR),
+            Language::Portuguese => Some(&PORTUGUESE_GRAMMAR),
+            Language::Italian => Some(&ITALIAN_GRAMMAR),
+        }
+    }
+
+    // Rewrites a localized hand into the English layout; English hands are returned unchanged
+    pub fn to_english(self, hand_str: &str) -> Cow<'_, str> {
+        // "1.250,50 €" or "€1.250,50"; chip counts without a symbol only lose their thousands separators
+        static COMMA_AMOUNT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?:([$€£₹]) ?(\d{1,3}(?:[ \x{a0}.]\d{3})*,\d{1,2})\b|(\d{1,3}(?:[ \x{a0}.]\d{3})*(?:,\d{1,2})?) ?([$€£₹]))").unwrap());
+        static THOUSANDS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\d{1,3}(?:\.\d{3})+\b").unwrap());
+        static DAY_FIRST_DATE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(\d{2})[./](\d{2})[./](\d{4})\b").unwrap());
+        static TIME_ZONE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d{1,2}:\d{2}:\d{2}) ([A-Z]{2,5})\b").unwrap());
+        static SEAT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^Seat \d+: (.+?) \([^()]* in chips").unwrap());
+
+        let Some(grammar) = self.grammar() else {
+            return Cow::Borrowed(hand_str);
+        };
+        let keywords = grammar.keywords;
+
+        let mut lines = Vec::new();
+        for (index, line) in hand_str.lines().enumerate() {
+            let mut line = line.to_string();
+            if index == 0 {
+                if keywords.day_first_dates {
+                    line = DAY_FIRST_DATE_RE.replace_all(&line, "$3/$2/$1").into_owned();
+                }
+                line = TIME_ZONE_RE.replace_all(&line, |caps: &Captures| {
+                    let zone = caps.get(2).unwrap().as_str();
+                    let zone = keywords.time_zones.iter().find(|(local, _)| *local == zone).map_or(zone, |(_, english)| english);
+                    format!("{} {}", caps.get(1).unwrap().as_str(), zone)
+                }).into_owned();
+            }
+            if keywords.decimal_comma {
+                line = COMMA_AMOUNT_RE.replace_all(&line, |caps: &Captures| {
+                    let symbol = caps.get(1).or(caps.get(4)).unwrap().as_str();
+                    let number = caps.get(2).or(caps.get(3)).unwrap().as_str().replace([' ', '\u{a0}', '.'], "").replace(',', ".");
+                    format!("{}{}", symbol, number)
+                }).into_owned();
+                line = THOUSANDS_RE.replace_all(&line, |caps: &Captures| caps[0].replace('.', "")).into_owned();
+            }
+            for (pattern, english) in &grammar.phrases {
+                if pattern.is_match(&line) {
+                    line = pattern.replace(&line, *english).into_owned();
+                }
+            }
+            lines.push(line);
+        }
+
+        // Verbs are only translated after a seated name, so a player called "mise" keeps their seat line
+        // and the summary's "Seat 3: ..." lines are never read as actions
+        let mut seated_names = lines.iter()
+            .filter_map(|line| SEAT_RE.captures(line).map(|caps| caps.get(1).unwrap().as_str().to_string()))
+            .collect::<Vec<String>>();
+        seated_names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        for line in lines.iter_mut().filter(|line| !SEAT_RE.is_match(line)) {
+            let action = seated_names.iter().find_map(|name| {
+                let caps = grammar.verb.captures(line.strip_prefix(name.as_str())?)?;
+                Some((name, caps.get(1).unwrap().as_str(), caps.get(2).unwrap().as_str()))
+            });
+            if let Some((name, verb, rest)) = action {
+                let english = keywords.actions.iter().find(|(local, _)| *local == verb).map_or(verb, |(_, english)| english);
+                let mut rest = rest.replace(keywords.all_in, " and is all-in");
+                if english == "raises" {
+                    rest = rest.replacen(keywords.raise_to, " to ", 1);
+                }
+                *line = format!("{}: {}{}", name, english, rest);
+            }
+        }
+        Cow::Owned(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::SeatOutcome;
+    use crate::money::Money;
+    use crate::{Action, Hand, ParseMode};
+
+    // Every localized hand below is this hand; {name} is a seated player named after one of that language's verbs
+    const ENGLISH: &str = "PokerStars Hand #250900000001:  Hold'em No Limit (€0.02/€0.05 EUR) - 2024/11/03 14:22:10 CET [2024/11/03 8:22:10 ET]
+Table 'Aarhus II' 6-max Seat #3 is the button
+Seat 1: PlayerOne (€5.12 in chips)
+Seat 2: Hero (€5 in chips)
+Seat 3: {name} (€4.87 in chips)
+Seat 4: Carl (€2.10 in chips)
+Seat 5: Dave (€6.33 in chips) is sitting out
+Seat 6: Eve (€5 in chips)
+Carl: posts small blind €0.02
+Eve: posts big blind €0.05
+*** HOLE CARDS ***
+Dealt to Hero [Ah Kd]
+PlayerOne: folds
+Hero: raises €0.10 to €0.15
+{name}: calls €0.15
+Carl: folds
+Eve: calls €0.10
+*** FLOP *** [7c 8d 2s]
+Eve: checks
+Hero: bets €0.30
+{name}: folds
+Eve: raises €4.55 to €4.85 and is all-in
+Hero: calls €4.55 and is all-in
+*** TURN *** [7c 8d 2s] [Kh]
+*** RIVER *** [7c 8d 2s Kh] [3c]
+*** SHOW DOWN ***
+Eve: shows [8h 8s] (three of a kind, Eights)
+Hero: mucks hand
+Eve collected €9.87 from pot
+*** SUMMARY ***
+Total pot €10.17 | Rake €0.30
+Board [7c 8d 2s Kh 3c]
+Seat 1: PlayerOne folded before Flop (didn't bet)
+Seat 2: Hero mucked [Ah Kd]
+Seat 3: {name} (button) folded on the Flop
+Seat 4: Carl (small blind) folded before Flop
+Seat 5: Dave is sitting out
+Seat 6: Eve (big blind) showed [8h 8s] and won (€9.87) with three of a kind, Eights";
+
+    const GERMAN_HAND: &str = "PokerStars Hand #250900000001:  Hold'em No Limit (0,02 €/0,05 € EUR) - 03.11.2024 14:22:10 MEZ [2024/11/03 8:22:10 ET]
+Tisch 'Aarhus II' 6-max Platz #3 ist der Button
+Platz 1: PlayerOne (5,12 € in Chips)
+Platz 2: Hero (5 € in Chips)
+Platz 3: setzt (4,87 € in Chips)
+Platz 4: Carl (2,10 € in Chips)
+Platz 5: Dave (6,33 € in Chips) ist nicht aktiv
+Platz 6: Eve (5 € in Chips)
+Carl: postet Small Blind 0,02 €
+Eve: postet Big Blind 0,05 €
+*** HOLE CARDS ***
+Ausgeteilt an Hero [Ah Kd]
+PlayerOne: passt
+Hero: erhöht 0,10 € auf 0,15 €
+setzt: callt 0,15 €
+Carl: passt
+Eve: callt 0,10 €
+*** FLOP *** [7c 8d 2s]
+Eve: checkt
+Hero: setzt 0,30 €
+setzt: passt
+Eve: erhöht 4,55 € auf 4,85 € und ist all-in
+Hero: callt 4,55 € und ist all-in
+*** TURN *** [7c 8d 2s] [Kh]
+*** RIVER *** [7c 8d 2s Kh] [3c]
+*** SHOWDOWN ***
+Eve: zeigt [8h 8s] (Drilling, Achten)
+Hero: wirft das Blatt weg
+Eve hat 9,87 € aus dem Pot gewonnen
+*** ZUSAMMENFASSUNG ***
+Gesamter Pot 10,17 € | Rake 0,30 €
+Board [7c 8d 2s Kh 3c]
+Platz 1: PlayerOne passte vor dem Flop (hat nicht gesetzt)
+Platz 2: Hero verdeckte [Ah Kd]
+Platz 3: setzt (Button) passte im Flop
+Platz 4: Carl (Small Blind) passte vor dem Flop
+Platz 5: Dave ist nicht aktiv
+Platz 6: Eve (Big Blind) zeigte [8h 8s] und gewann (9,87 €) mit Drilling, Achten";
+
+    const FRENCH_HAND: &str = "PokerStars Main n°250900000001:  Hold'em No Limit (0,02 €/0,05 € EUR) - 03/11/2024 14:22:10 HEC [2024/11/03 8:22:10 ET]
+Table 'Aarhus II' 6-max Siège n°3 est le bouton
+Siège 1 : PlayerOne (5,12 € en jetons)
+Siège 2 : Hero (5 € en jetons)
+Siège 3 : mise (4,87 € en jetons)
+Siège 4 : Carl (2,10 € en jetons)
+Siège 5 : Dave (6,33 € en jetons) est absent
+Siège 6 : Eve (5 € en jetons)
+Carl : poste la petite blind 0,02 €
+Eve : poste la grosse blind 0,05 €
+*** CARTES FERMÉES ***
+Distribuées à Hero [Ah Kd]
+PlayerOne : passe
+Hero : relance 0,10 € à 0,15 €
+mise : suit 0,15 €
+Carl : passe
+Eve : suit 0,10 €
+*** FLOP *** [7c 8d 2s]
+Eve : checke
+Hero : mise 0,30 €
+mise : passe
+Eve : relance 4,55 € à 4,85 € et est à tapis
+Hero : suit 4,55 € et est à tapis
+*** TOURNANT *** [7c 8d 2s] [Kh]
+*** RIVIÈRE *** [7c 8d 2s Kh] [3c]
+*** ABATTAGE ***
+Eve : montre [8h 8s] (brelan de huit)
+Hero : jette la main
+Eve a remporté 9,87 € du pot
+*** RÉSUMÉ ***
+Pot total 10,17 € | Commission 0,30 €
+Tableau [7c 8d 2s Kh 3c]
+Siège 1 : PlayerOne s'est couché avant le Flop (n'a pas misé)
+Siège 2 : Hero a jeté [Ah Kd]
+Siège 3 : mise (bouton) s'est couché au Flop
+Siège 4 : Carl (petite blind) s'est couché avant le Flop
+Siège 5 : Dave est absent
+Siège 6 : Eve (grosse blind) a montré [8h 8s] et a gagné (9,87 €) avec brelan de huit";
+
+    const SPANISH_HAND: &str = "PokerStars Mano n.º250900000001:  Hold'em No Limit (0,02 €/0,05 € EUR) - 2024/11/03 14:22:10 CET [2024/11/03 8:22:10 ET]
+Mesa 'Aarhus II' 6-max Asiento #3 es el botón
+Asiento 1: PlayerOne (5,12 € en fichas)
+Asiento 2: Hero (5 € en fichas)
+Asiento 3: sube (4,87 € en fichas)
+Asiento 4: Carl (2,10 € en fichas)
+Asiento 5: Dave (6,33 € en fichas) está ausente
+Asiento 6: Eve (5 € en fichas)
+Carl: pone la ciega pequeña 0,02 €
+Eve: pone la ciega grande 0,05 €
+*** CARTAS DE MANO ***
+Repartidas a Hero [Ah Kd]
+PlayerOne: se retira
+Hero: sube 0,10 € a 0,15 €
+sube: iguala 0,15 €
+Carl: se retira
+Eve: iguala 0,10 €
+*** FLOP *** [7c 8d 2s]
+Eve: pasa
+Hero: apuesta 0,30 €
+sube: se retira
+Eve: sube 4,55 € a 4,85 € y va all-in
+Hero: iguala 4,55 € y va all-in
+*** TURN *** [7c 8d 2s] [Kh]
+*** RIVER *** [7c 8d 2s Kh] [3c]
+*** ENFRENTAMIENTO ***
+Eve: muestra [8h 8s] (trío de ochos)
+Hero: descarta la mano
+Eve se llevó 9,87 € del bote
+*** RESUMEN ***
+Bote total 10,17 € | Comisión 0,30 €
+Mesa [7c 8d 2s Kh 3c]
+Asiento 1: PlayerOne se retiró antes del Flop (no apostó)
+Asiento 2: Hero descartó [Ah Kd]
+Asiento 3: sube (botón) se retiró en el Flop
+Asiento 4: Carl (ciega pequeña) se retiró antes del Flop
+Asiento 5: Dave está ausente
+Asiento 6: Eve (ciega grande) mostró [8h 8s] y ganó (9,87 €) con trío de ochos";
+
+    const PORTUGUESE_HAND: &str = "PokerStars Mão n.º250900000001:  Hold'em No Limit (0,02 €/0,05 € EUR) - 2024/11/03 10:22:10 BRT [2024/11/03 8:22:10 ET]
+Mesa 'Aarhus II' 6-max Lugar #3 é o botão
+Lugar 1: PlayerOne (5,12 € em fichas)
+Lugar 2: Hero (5 € em fichas)
+Lugar 3: aposta (4,87 € em fichas)
+Lugar 4: Carl (2,10 € em fichas)
+Lugar 5: Dave (6,33 € em fichas) está ausente
+Lugar 6: Eve (5 € em fichas)
+Carl: paga o small blind 0,02 €
+Eve: paga o big blind 0,05 €
+*** CARTAS DA MÃO ***
+Cartas de Hero [Ah Kd]
+PlayerOne: desiste
+Hero: aumenta 0,10 € para 0,15 €
+aposta: iguala 0,15 €
+Carl: desiste
+Eve: iguala 0,10 €
+*** FLOP *** [7c 8d 2s]
+Eve: passa
+Hero: aposta 0,30 €
+aposta: desiste
+Eve: aumenta 4,55 € para 4,85 € e está all-in
+Hero: iguala 4,55 € e está all-in
+*** TURN *** [7c 8d 2s] [Kh]
+*** RIVER *** [7c 8d 2s Kh] [3c]
+*** CONFRONTO ***
+Eve: mostra [8h 8s] (trinca de oitos)
+Hero: descarta a mão
+Eve recebeu 9,87 € do pote
+*** RESUMO ***
+Pote total 10,17 € | Taxa 0,30 €
+Bordo [7c 8d 2s Kh 3c]
+Lugar 1: PlayerOne desistiu antes do Flop (não apostou)
+Lugar 2: Hero descartou [Ah Kd]
+Lugar 3: aposta (botão) desistiu no Flop
+Lugar 4: Carl (small blind) desistiu antes do Flop
+Lugar 5: Dave está ausente
+Lugar 6: Eve (big blind) mostrou [8h 8s] e ganhou (9,87 €) com trinca de oitos";
+
+    const ITALIAN_HAND: &str = "PokerStars Mano n.250900000001:  Hold'em No Limit (0,02 €/0,05 € EUR) - 03/11/2024 14:22:10 CET [2024/11/03 8:22:10 ET]
+Tavolo 'Aarhus II' 6-max Posto #3 è il bottone
+Posto 1: PlayerOne (5,12 € in fiches)
+Posto 2: Hero (5 € in fiches)
+Posto 3: punta (4,87 € in fiches)
+Posto 4: Carl (2,10 € in fiches)
+Posto 5: Dave (6,33 € in fiches) è seduto fuori
+Posto 6: Eve (5 € in fiches)
+Carl: paga lo small blind 0,02 €
+Eve: paga il big blind 0,05 €
+*** CARTE COPERTE ***
+Carte distribuite a Hero [Ah Kd]
+PlayerOne: passa
+Hero: rilancia 0,10 € a 0,15 €
+punta: chiama 0,15 €
+Carl: passa
+Eve: chiama 0,10 €
+*** FLOP *** [7c 8d 2s]
+Eve: fa check
+Hero: punta 0,30 €
+punta: passa
+Eve: rilancia 4,55 € a 4,85 € ed è all-in
+Hero: chiama 4,55 € ed è all-in
+*** TURN *** [7c 8d 2s] [Kh]
+*** RIVER *** [7c 8d 2s Kh] [3c]
+*** SHOWDOWN ***
+Eve: mostra [8h 8s] (tris di otto)
+Hero: scarta la mano
+Eve ha incassato 9,87 € dal piatto
+*** RIEPILOGO ***
+Piatto totale 10,17 € | Rake 0,30 €
+Board [7c 8d 2s Kh 3c]
+Posto 1: PlayerOne ha passato prima del Flop (non ha puntato)
+Posto 2: Hero ha scartato [Ah Kd]
+Posto 3: punta (bottone) ha passato al Flop
+Posto 4: Carl (small blind) ha passato prima del Flop
+Posto 5: Dave è seduto fuori
+Posto 6: Eve (big blind) ha mostrato [8h 8s] e ha vinto (9,87 €) con tris di otto";
+
+    // Hand descriptions stay in the client's language, so shown cards are compared without them
+    fn without_description(action: &Action) -> String {
+        match action {
+            Action::Shows(cards, _) => Action::Shows(cards.clone(), String::new()).to_json(),
+            action => action.to_json(),
+        }
+    }
+
+    fn without_description_outcome(outcome: &SeatOutcome) -> SeatOutcome {
+        match outcome {
+            SeatOutcome::Showed { cards, won, .. } => SeatOutcome::Showed { cards: cards.clone(), won: *won, description: None },
+            outcome => outcome.clone(),
+        }
+    }
+
+    fn assert_parses_like_english(localized_str: &str, language: Language, verb_name: &str) {
+        assert_eq!(Language::detect(localized_str), language);
+        let localized = Hand::from_str(localized_str, ParseMode::Strict).unwrap_or_else(|e| panic!("{:?}: {}", language, e));
+        let english = Hand::from_str(&ENGLISH.replace("{name}", verb_name), ParseMode::Strict).unwrap();
+
+        assert_eq!(localized.language, language);
+        assert_eq!(localized.id, english.id);
+        assert_eq!(localized.timestamp, english.timestamp);
+        assert_eq!(localized.table_name, english.table_name);
+        assert_eq!(localized.game_info.to_json(), english.game_info.to_json());
+        assert_eq!(localized.hero_name, english.hero_name);
+        assert_eq!(localized.hero_cards, english.hero_cards);
+        assert_eq!(localized.total_pot, english.total_pot);
+        assert_eq!(localized.rake, english.rake);
+        assert_eq!(localized.unknown_lines, 0);
+
+        let players = |hand: &Hand| hand.players.iter()
+            .map(|player| (player.seat, player.name.clone(), player.chips, player.chips_after_hand, player.is_sitting_out, player.position.as_ref().map(|position| position.as_str().to_string())))
+            .collect::<Vec<_>>();
+        assert_eq!(players(&localized), players(&english));
+
+        let actions = |hand: &Hand| hand.actions().all().map(|action| (action.player_name.clone(), without_description(&action.action))).collect::<Vec<_>>();
+        assert_eq!(actions(&localized), actions(&english));
+
+        let summary = |hand: &Hand| hand.summary.iter().map(|seat| (seat.seat, seat.name.clone(), without_description_outcome(&seat.outcome))).collect::<Vec<_>>();
+        assert_eq!(summary(&localized), summary(&english));
+        assert!(localized.summary.iter().all(|seat| !matches!(seat.outcome, SeatOutcome::Other(_))));
+        assert!(localized.validate(None).is_empty());
+    }
+
+    #[test]
+    fn parses_german_hands_like_english() {
+        assert_parses_like_english(GERMAN_HAND, Language::German, "setzt");
+    }
+
+    #[test]
+    fn parses_french_hands_like_english() {
+        assert_parses_like_english(FRENCH_HAND, Language::French, "mise");
+    }
+
+    #[test]
+    fn parses_spanish_hands_like_english() {
+        assert_parses_like_english(SPANISH_HAND, Language::Spanish, "sube");
+    }
+
+    #[test]
+    fn parses_portuguese_hands_like_english() {
+        assert_parses_like_english(PORTUGUESE_HAND, Language::Portuguese, "aposta");
+    }
+
+    #[test]
+    fn parses_italian_hands_like_english() {
+        assert_parses_like_english(ITALIAN_HAND, Language::Italian, "punta");
+    }
+
+    // Adds a dead small and big blind posted by Hero and a straddle by the verb-named player after the big blind
+    fn assert_parses_dead_blinds_and_straddles(localized_str: &str, language: Language, verb_name: &str, posts: [&str; 2]) {
+        let mut lines = localized_str.lines().collect::<Vec<&str>>();
+        let big_blind_at = lines.iter().position(|line| line.starts_with("Eve:") || line.starts_with("Eve :")).unwrap();
+        lines.splice(big_blind_at + 1..big_blind_at + 1, posts);
+        let hand = Hand::from_str(&lines.join("\n"), ParseMode::Strict).unwrap_or_else(|e| panic!("{:?}: {}", language, e));
+
+        let posted = |name: &str, action: fn(&Action) -> bool| hand.pre_actions.iter().any(|posted| posted.player_name == name && action(&posted.action));
+        assert!(posted("Hero", |action| matches!(action, Action::PostSmallAndBigBlind(amount) if *amount == Money::from_cents(7))), "{:?}", language);
+        assert!(posted(verb_name, |action| matches!(action, Action::PostStraddle(amount) if *amount == Money::from_cents(10))), "{:?}", language);
+        assert_eq!(hand.unknown_lines, 0);
+    }
+
+    #[test]
+    fn parses_localized_dead_blinds_and_straddles() {
+        assert_parses_dead_blinds_and_straddles(GERMAN_HAND, Language::German, "setzt", ["Hero: postet Small & Big Blind 0,07 €", "setzt: postet Straddle 0,10 €"]);
+        assert_parses_dead_blinds_and_straddles(FRENCH_HAND, Language::French, "mise", ["Hero : poste la petite et la grosse blind 0,07 €", "mise : poste un straddle 0,10 €"]);
+        assert_parses_dead_blinds_and_straddles(SPANISH_HAND, Language::Spanish, "sube", ["Hero: pone las ciegas pequeña y grande 0,07 €", "sube: pone un straddle 0,10 €"]);
+        assert_parses_dead_blinds_and_straddles(PORTUGUESE_HAND, Language::Portuguese, "aposta", ["Hero: paga o small e o big blind 0,07 €", "aposta: paga o straddle 0,10 €"]);
+        assert_parses_dead_blinds_and_straddles(ITALIAN_HAND, Language::Italian, "punta", ["Hero: paga lo small e il big blind 0,07 €", "punta: paga lo straddle 0,10 €"]);
+    }
+
+    #[test]
+    fn leaves_english_hands_unchanged() {
+        let english = ENGLISH.replace("{name}", "Bob");
+        assert_eq!(Language::detect(&english), Language::English);
+        assert!(matches!(Language::English.to_english(&english), Cow::Borrowed(_)));
+    }
+}
@@ -1,5 +1,5 @@
-use std::{fs::{self, DirEntry}, io::Error, thread, sync::mpsc};
-use regex::Regex;
+use std::{collections::HashMap, fs::{self, DirEntry}, io::Error, thread, sync::{mpsc, LazyLock}};
+use regex::{Regex, RegexSet};
 use reqwest;
 use tray_icon::{menu::{CheckMenuItem, IconMenuItem, Menu, MenuEvent, MenuItem}, Icon, TrayIconBuilder, TrayIconEvent};
 #[cfg(target_os = "linux")]
@@ -9,8 +9,32 @@ use dotenv::dotenv;
 use native_dialog::{DialogBuilder, MessageLevel};
 mod auth;
 mod config;
+mod parse_error;
+mod bench;
+mod game_info;
+mod amount;
+mod card;
+mod money;
+mod validation;
+mod timeline;
+mod summary;
+mod hand_file;
+mod timestamp;
+mod language;
+mod session;
 use auth::{start_login_flow, get_access_token, store_access_token, get_google_user_info};
 use config::{ConfigManager, AppConfig};
+use parse_error::{HandParseError, HandParseErrorKind};
+use game_info::{GameInfo, HandKind, LimitType};
+use amount::parse_amount;
+use card::{Card, parse_cards, join_cards};
+use money::Money;
+use validation::ValidationIssue;
+use timeline::build_timeline;
+use summary::{SeatSummary, SeatOutcome};
+use hand_file::{read_hand_file, split_hands};
+use language::Language;
+use session::group_sessions;
 use std::env;
 use winreg::{RegKey, enums::HKEY_CLASSES_ROOT};
 
@@ -30,6 +54,7 @@ enum Position {
     UTG,
     UTG1,
     UTG2,
+    UTG3,
     LJ,
     HJ,
     CO,
@@ -44,24 +69,25 @@ impl Position {
             Position::UTG => "UTG",
             Position::UTG1 => "UTG1",
             Position::UTG2 => "UTG2",
+            Position::UTG3 => "UTG3",
             Position::LJ => "LJ",
             Position::HJ => "HJ",
             Position::CO => "CO",
         }
     }
 
-    fn from_index(index: usize) -> Position {
-        match index {
-            0 => Position::BTN,
-            1 => Position::SB,
-            2 => Position::BB,
-            3 => Position::UTG,
-            4 => Position::UTG1,
-            5 => Position::UTG2,
-            6 => Position::LJ,
-            7 => Position::HJ,
-            8 => Position::CO,
-            _ => panic!("Invalid index: {}", index),
+    // Labels for the players between the big blind and the button, in the order they act preflop.
+    // The last seats are always CO, HJ, LJ so a 6 handed table reads UTG, HJ, CO, BTN, SB, BB
+    fn before_button(count: usize) -> Vec<Position> {
+        match count {
+            0 => vec![],
+            1 => vec![Position::UTG],
+            2 => vec![Position::UTG, Position::CO],
+            3 => vec![Position::UTG, Position::HJ, Position::CO],
+            4 => vec![Position::UTG, Position::LJ, Position::HJ, Position::CO],
+            5 => vec![Position::UTG, Position::UTG1, Position::LJ, Position::HJ, Position::CO],
+            6 => vec![Position::UTG, Position::UTG1, Position::UTG2, Position::LJ, Position::HJ, Position::CO],
+            _ => vec![Position::UTG, Position::UTG1, Position::UTG2, Position::UTG3, Position::LJ, Position::HJ, Position::CO],
         }
     }
 
@@ -75,9 +101,12 @@ struct Player {
     seat: u32,
     position: Option<Position>,
     name: String,
-    chips: f32,
-    chips_after_hand: f32,
+    chips: Money,
+    chips_after_hand: Money,
     is_sitting_out: bool,
+    is_hero: bool,
+    hole_cards: Option<Vec<Card>>, // hero's cards, or what the player showed or mucked face up; may be partial
+    hand_description: Option<String>, // e.g. "a pair of Aces", when the player showed down
 }
 
 impl Player {
@@ -88,7 +117,7 @@ impl Player {
 
     fn to_json(&self) -> String {
         format!(
-            "{{\"seat\":{},\"position\":{},\"name\":\"{}\",\"chips\":{},\"chips_after_hand\":{},\"is_sitting_out\":{}}}",
+            "{{\"seat\":{},\"position\":{},\"name\":\"{}\",\"chips\":{},\"chips_after_hand\":{},\"is_sitting_out\":{},\"is_hero\":{},\"hole_cards\":{},\"hand_description\":{}}}",
             self.seat,
             match &self.position {
                 Some(pos) => pos.to_json(),
@@ -97,156 +126,393 @@ impl Player {
             self.name.replace("\"", "\\\""),
             self.chips,
             self.chips_after_hand,
-            self.is_sitting_out
+            self.is_sitting_out,
+            self.is_hero,
+            match &self.hole_cards {
+                Some(cards) => format!("[{}]", cards.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(",")),
+                None => "null".to_string(),
+            },
+            match &self.hand_description {
+                Some(description) => format!("\"{}\"", description.replace("\"", "\\\"")),
+                None => "null".to_string(),
+            }
+        )
+    }
+}
+
+// One run of the community cards; hands that are run twice have two
+#[derive(Debug, Clone)]
+struct Board {
+    cards: Vec<Card>,
+    winners: Vec<(String, Money)>, // player name and their share of the pot on this board
+}
+
+impl Board {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"cards\":[{}],\"winners\":[{}]}}",
+            self.cards.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(","),
+            self.winners.iter().map(|(name, amount)| format!("{{\"player_name\":\"{}\",\"amount\":{}}}", name.replace("\"", "\\\""), amount)).collect::<Vec<_>>().join(",")
+        )
+    }
+}
+
+// The main pot is pots[0], side pots follow in the order PokerStars numbers them
+#[derive(Debug, Clone)]
+struct Pot {
+    amount: Money,
+    eligible_players: Vec<String>,
+    winners: Vec<(String, Money)>,
+}
+
+impl Pot {
+    fn to_json(&self) -> String {
+        format!(
+            "{{\"amount\":{},\"eligible_players\":[{}],\"winners\":[{}]}}",
+            self.amount,
+            self.eligible_players.iter().map(|name| format!("\"{}\"", name.replace("\"", "\\\""))).collect::<Vec<_>>().join(","),
+            self.winners.iter().map(|(name, amount)| format!("{{\"player_name\":\"{}\",\"amount\":{}}}", name.replace("\"", "\\\""), amount)).collect::<Vec<_>>().join(",")
         )
     }
 }
 
 #[derive(Debug, Clone)]
 enum Action {
-    PostSmallBlind(f32),
-    PostBigBlind(f32),
+    PostSmallBlind(Money),
+    PostBigBlind(Money),
     SitsOut,
     Fold,
-    Call(f32),
-    Raise(f32,f32),
+    Call(Money),
+    Raise(Money,Money),
     Check,
-    Bet(f32),
-    BetAndAllIn(f32),
-    CallAndAllIn(f32),
-    RaiseAndAllIn(f32,f32),
+    Bet(Money),
+    BetAndAllIn(Money),
+    CallAndAllIn(Money),
+    RaiseAndAllIn(Money,Money),
     Muck,
-    Shows(Vec<String>, String),
-    Collected(f32),
-    CashedOut(f32, f32),
+    Shows(Vec<Card>, String),
+    Collected(Money),
+    CashedOut(Money, Money),
     TimedOut,
-    UncalledBet(f32),
+    UncalledBet(Money),
     DoesNotShow,
     Join,
     Leave,
     Disconnected,
     Connected,
-    CollectedFromSidePot(f32),
-    CollectedFromMainPot(f32),
+    CollectedFromSidePot(Money, u32), // amount, side pot number (1 for a lone "side pot")
+    CollectedFromMainPot(Money),
+    PostSmallAndBigBlind(Money),
+    Chat(String),
+    Returned,
+    IsSittingOut,
+    WaitsForButton,
+    AddsChips(Money),
+    PostAnte(Money),
+    PostStraddle(Money),
+    FinishedTournament(Option<u32>, Money), // place, prize
+    WonBounty(Money, String), // amount, eliminated player
+    Unknown { raw: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Street {
+    Pre,
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    ShowDown,
+}
+
+impl Street {
+    fn as_str(&self) -> &str {
+        match self {
+            Street::Pre => "Pre",
+            Street::Preflop => "Preflop",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+            Street::ShowDown => "ShowDown",
+        }
+    }
+}
+
+// Strict fails the whole hand on the first unrecognised line,
+// Lenient keeps such lines as Action::Unknown so new PokerStars formats don't lose the hand
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParseMode {
+    Strict,
+    Lenient,
 }
 
 struct ActionToParse {
-    re: Regex,
-    action_type: fn(caps: regex::Captures<'_>) -> Action,
+    pattern: &'static str, // the part of the line after the player name, "{amount}" stands for amount::AMOUNT
+    action_type: fn(caps: regex::Captures<'_>) -> Option<Action>,
 }   
 
+// Compiled once: a RegexSet picks the matching rule, then only that rule's captures are run
+struct ActionGrammar {
+    regex_set: RegexSet,
+    regexes: Vec<Regex>,
+    actions_to_parse: Vec<ActionToParse>,
+}
+
+impl ActionGrammar {
+    fn new(actions_to_parse: Vec<ActionToParse>) -> Self {
+        let patterns = actions_to_parse.iter().map(|action_to_parse| format!("^{}$", amount::expand(action_to_parse.pattern))).collect::<Vec<_>>();
+        ActionGrammar {
+            regex_set: RegexSet::new(&patterns).unwrap(),
+            regexes: patterns.iter().map(|pattern| Regex::new(pattern).unwrap()).collect(),
+            actions_to_parse,
+        }
+    }
+
+    // Parses what follows the player name, e.g. ": raises $0.10 to $0.15"
+    fn parse(&self, rest: &str) -> Result<Action, HandParseErrorKind> {
+        let index = self.regex_set.matches(rest).iter().next().ok_or(HandParseErrorKind::UnknownAction)?;
+        let caps = self.regexes[index].captures(rest).unwrap();
+        (self.actions_to_parse[index].action_type)(caps).ok_or(HandParseErrorKind::InvalidNumber)
+    }
+}
+
 #[derive(Debug, Clone)]
 struct PlayerAction {
     player_name: String,
     action: Action,
 }
 
+// A hand's actions street by street, for the calculations that replay the whole hand
+#[derive(Clone, Copy)]
+struct HandActions<'a> {
+    pre: &'a [PlayerAction],
+    preflop: &'a [PlayerAction],
+    flop: &'a [PlayerAction],
+    turn: &'a [PlayerAction],
+    river: &'a [PlayerAction],
+    show_down: &'a [PlayerAction],
+}
+
+impl<'a> HandActions<'a> {
+    fn all(self) -> impl Iterator<Item = &'a PlayerAction> + Clone {
+        self.pre.iter().chain(self.preflop).chain(self.flop).chain(self.turn).chain(self.river).chain(self.show_down)
+    }
+}
+
 impl PlayerAction {
-    fn from_str(line: &str) -> Option<Self> {
-        let actions_to_parse = vec![
-            ActionToParse {
-                re: Regex::new(r"(.+?): folds").unwrap(),
-                action_type: |caps| Action::Fold
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): checks").unwrap(), 
-                action_type: |caps| Action::Check
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): bets \$([0-9.]+)").unwrap(),
-                action_type: |caps| Action::Bet(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): calls \$([0-9.]+)").unwrap(),
-                action_type: |caps| Action::Call(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): raises \$([0-9.]+) to \$([0-9.]+)").unwrap(),
-                action_type: |caps| Action::Raise(caps.get(2).unwrap().as_str().parse::<f32>().unwrap(), caps.get(3).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): bets \$([0-9.]+) and is all-in").unwrap(),
-                action_type: |caps| Action::BetAndAllIn(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): calls \$([0-9.]+) and is all-in").unwrap(),
-                action_type: |caps| Action::CallAndAllIn(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): raises \$([0-9.]+) to \$([0-9.]+) and is all-in").unwrap(),
-                action_type: |caps| Action::RaiseAndAllIn(caps.get(2).unwrap().as_str().parse::<f32>().unwrap(), caps.get(3).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): shows \[([2-9TJQKA][cdhs]) ([2-9TJQKA][cdhs])\] \((.+?)\)").unwrap(),
-                action_type: |caps| Action::Shows(vec![caps.get(2).unwrap().as_str().to_string(), caps.get(3).unwrap().as_str().to_string()], caps.get(4).unwrap().as_str().to_string())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): mucks hand").unwrap(),
-                action_type: |caps| Action::Muck
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) collected \$([0-9.]+) from pot").unwrap(),
-                action_type: |caps| Action::Collected(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) cashed out the hand for \$([0-9.]+)(?:\s+\| Cash Out Fee \$([0-9.]+))?").unwrap(),
-                action_type: |caps| Action::CashedOut(
-                    caps.get(2).unwrap().as_str().parse::<f32>().unwrap(),
-                    caps.get(3).map_or(0.0, |m| m.as_str().parse::<f32>().unwrap())
-                )
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) has timed out").unwrap(),
-                action_type: |caps| Action::TimedOut
-            },
-            ActionToParse {
-                re: Regex::new(r"Uncalled bet \(\$([0-9.]+)\) returned to (.+?)").unwrap(),
-                action_type: |caps| Action::UncalledBet(caps.get(1).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?): doesn't show hand").unwrap(),
-                action_type: |caps| Action::DoesNotShow
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) joins the table").unwrap(),
-                action_type: |caps| Action::Join
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) leaves the table").unwrap(),
-                action_type: |caps| Action::Leave
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) is disconnected").unwrap(),
-                action_type: |caps| Action::Disconnected
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) is connected").unwrap(),
-                action_type: |caps| Action::Connected
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) collected \$([0-9.]+) from side pot").unwrap(),
-                action_type: |caps| Action::CollectedFromSidePot(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-            ActionToParse {
-                re: Regex::new(r"(.+?) collected \$([0-9.]+) from main pot").unwrap(),
-                action_type: |caps| Action::CollectedFromMainPot(caps.get(2).unwrap().as_str().parse::<f32>().unwrap())
-            },
-        ];
+    fn from_str(line: &str, seated_names: &[String]) -> Result<Self, HandParseError> {
+        static UNCALLED_BET_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"^Uncalled bet \({amount}\) returned to (.+)$")).unwrap());
+        static ACTION_GRAMMAR: LazyLock<ActionGrammar> = LazyLock::new(|| {
+            let actions_to_parse = vec![
+                ActionToParse {
+                    pattern: r#" said, "(.*)""#,
+                    action_type: |caps| Some(Action::Chat(caps.get(1).unwrap().as_str().to_string()))
+                },
+                ActionToParse {
+                    pattern: r": folds(?: \[.+\])?",
+                    action_type: |caps| Some(Action::Fold)
+                },
+                ActionToParse {
+                    pattern: r": checks",
+                    action_type: |caps| Some(Action::Check)
+                },
+                ActionToParse {
+                    pattern: r": bets {amount} and is all-in",
+                    action_type: |caps| Some(Action::BetAndAllIn(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": bets {amount}",
+                    action_type: |caps| Some(Action::Bet(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": calls {amount} and is all-in",
+                    action_type: |caps| Some(Action::CallAndAllIn(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": calls {amount}",
+                    action_type: |caps| Some(Action::Call(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": raises {amount} to {amount} and is all-in",
+                    action_type: |caps| Some(Action::RaiseAndAllIn(parse_amount(&caps, 1)?, parse_amount(&caps, 2)?))
+                },
+                ActionToParse {
+                    pattern: r": raises {amount} to {amount}",
+                    action_type: |caps| Some(Action::Raise(parse_amount(&caps, 1)?, parse_amount(&caps, 2)?))
+                },
+                ActionToParse {
+                    pattern: r": shows \[((?:[2-9TJQKA][cdhs] ?)+)\] \((.+?)\)",
+                    action_type: |caps| Some(Action::Shows(parse_cards(caps.get(1).unwrap().as_str())?, caps.get(2).unwrap().as_str().to_string()))
+                },
+                ActionToParse {
+                    pattern: r": mucks hand",
+                    action_type: |caps| Some(Action::Muck)
+                },
+                ActionToParse {
+                    pattern: r" collected {amount} from pot",
+                    action_type: |caps| Some(Action::Collected(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r" cashed out the hand for {amount}(?:\s+\| Cash Out Fee {amount})?",
+                    action_type: |caps| Some(Action::CashedOut(
+                        parse_amount(&caps, 1)?,
+                        if caps.get(2).is_some() { parse_amount(&caps, 2)? } else { Money::ZERO }
+                    ))
+                },
+                ActionToParse {
+                    pattern: r" has timed out(?: while (?:being )?disconnected)?",
+                    action_type: |caps| Some(Action::TimedOut)
+                },
+                ActionToParse {
+                    pattern: r": doesn't show hand",
+                    action_type: |caps| Some(Action::DoesNotShow)
+                },
+                ActionToParse {
+                    pattern: r" joins the table(?: at seat #\d+)?",
+                    action_type: |caps| Some(Action::Join)
+                },
+                ActionToParse {
+                    pattern: r" leaves the table",
+                    action_type: |caps| Some(Action::Leave)
+                },
+                ActionToParse {
+                    pattern: r" is disconnected",
+                    action_type: |caps| Some(Action::Disconnected)
+                },
+                ActionToParse {
+                    pattern: r" is connected",
+                    action_type: |caps| Some(Action::Connected)
+                },
+                ActionToParse {
+                    pattern: r" collected {amount} from side pot(?:-(\d+))?",
+                    action_type: |caps| Some(Action::CollectedFromSidePot(
+                        parse_amount(&caps, 1)?,
+                        match caps.get(2) { Some(number) => number.as_str().parse::<u32>().ok()?, None => 1 }
+                    ))
+                },
+                ActionToParse {
+                    pattern: r" collected {amount} from main pot",
+                    action_type: |caps| Some(Action::CollectedFromMainPot(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": posts small blind {amount}(?: and is all-in)?",
+                    action_type: |caps| Some(Action::PostSmallBlind(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": posts big blind {amount}(?: and is all-in)?",
+                    action_type: |caps| Some(Action::PostBigBlind(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": posts small & big blinds {amount}(?: and is all-in)?",
+                    action_type: |caps| Some(Action::PostSmallAndBigBlind(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": posts the ante {amount}(?: and is all-in)?",
+                    action_type: |caps| Some(Action::PostAnte(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": posts straddle {amount}(?: and is all-in)?",
+                    action_type: |caps| Some(Action::PostStraddle(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r": sits out",
+                    action_type: |caps| Some(Action::SitsOut)
+                },
+                ActionToParse {
+                    pattern: r" has returned",
+                    action_type: |caps| Some(Action::Returned)
+                },
+                ActionToParse {
+                    pattern: r" is sitting out",
+                    action_type: |caps| Some(Action::IsSittingOut)
+                },
+                ActionToParse {
+                    pattern: r" will be allowed to play after the button",
+                    action_type: |caps| Some(Action::WaitsForButton)
+                },
+                ActionToParse {
+                    pattern: r" adds {amount}(?: \(.+\))?",
+                    action_type: |caps| Some(Action::AddsChips(parse_amount(&caps, 1)?))
+                },
+                ActionToParse {
+                    pattern: r" finished the tournament(?: in (\d+)(?:st|nd|rd|th) place)?(?: and received {amount}\.)?",
+                    action_type: |caps| Some(Action::FinishedTournament(
+                        match caps.get(1) { Some(place) => Some(place.as_str().parse::<u32>().ok()?), None => None },
+                        if caps.get(2).is_some() { parse_amount(&caps, 2)? } else { Money::ZERO }
+                    ))
+                },
+                ActionToParse {
+                    pattern: r" wins the tournament(?: and receives {amount})? - congratulations!",
+                    action_type: |caps| Some(Action::FinishedTournament(Some(1), if caps.get(1).is_some() { parse_amount(&caps, 1)? } else { Money::ZERO }))
+                },
+                ActionToParse {
+                    pattern: r" wins {amount} for eliminating (.+?) and (?:their|his|her) own bounty increases by {amount} to {amount}",
+                    action_type: |caps| Some(Action::WonBounty(parse_amount(&caps, 1)?, caps.get(2).unwrap().as_str().to_string()))
+                },
+                ActionToParse {
+                    pattern: r" wins the {amount} bounty for eliminating (.+)",
+                    action_type: |caps| Some(Action::WonBounty(parse_amount(&caps, 1)?, caps.get(2).unwrap().as_str().to_string()))
+                },
+            ];
+            ActionGrammar::new(actions_to_parse)
+        });
+
+        let line = line.trim_end();
+
+        if let Some(caps) = UNCALLED_BET_RE.captures(line) {
+            // The line was trimmed, so compare against trimmed names to keep trailing spaces working
+            let player_name = seated_names.iter()
+                .find(|name| name.trim_end() == caps.get(2).unwrap().as_str())
+                .ok_or_else(|| HandParseError::new(HandParseErrorKind::UnseatedPlayer, line))?;
+            let amount = parse_amount(&caps, 1).ok_or_else(|| HandParseError::new(HandParseErrorKind::InvalidNumber, line))?;
+            return Ok(PlayerAction {
+                player_name: player_name.clone(),
+                action: Action::UncalledBet(amount),
+            });
+        }
 
-        for action_to_parse in actions_to_parse {
-            if let Some(caps) = action_to_parse.re.captures(line) {
-                let player_name = caps.get(1).unwrap().as_str().to_string();
+        // The longest seated name that prefixes the line and leaves a known action wins,
+        // so names containing ": ", " collected ", brackets etc. are not split in the wrong place
+        let mut candidate_names = seated_names.iter().filter(|name| !name.is_empty() && line.starts_with(name.as_str())).collect::<Vec<_>>();
+        candidate_names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        for name in candidate_names {
+            match ACTION_GRAMMAR.parse(&line[name.len()..]) {
+                Err(HandParseErrorKind::UnknownAction) => continue,
+                result => {
+                    return result
+                        .map(|action| PlayerAction { player_name: name.to_string(), action })
+                        .map_err(|kind| HandParseError::new(kind, line));
+                }
+            }
+        }
 
-                return Some(PlayerAction {
-                    player_name,
-                    action: (action_to_parse.action_type)(caps),
-                });
+        // Players who are not in the seat list can only join, chat, wait for the button etc.
+        for (index, _) in line.char_indices().skip(1) {
+            match ACTION_GRAMMAR.parse(&line[index..]) {
+                Err(HandParseErrorKind::UnknownAction) => continue,
+                Err(kind) => return Err(HandParseError::new(kind, line)),
+                Ok(action) if action.requires_seat() => return Err(HandParseError::new(HandParseErrorKind::UnseatedPlayer, line)),
+                Ok(action) => {
+                    return Ok(PlayerAction {
+                        player_name: line[..index].to_string(),
+                        action,
+                    });
+                }
             }
-        };
-        //println!("!!!!!!!!!!!!!Warning: Could not parse action from line: {}", line);
-        panic!("!!!!!!!!!!!!!Warning: Could not parse action from line: {}", line);
-        None
+        }
+        Err(HandParseError::new(HandParseErrorKind::UnknownAction, line))
     }
+
+    fn from_str_with_mode(line: &str, seated_names: &[String], mode: ParseMode) -> Result<Self, HandParseError> {
+        match Self::from_str(line, seated_names) {
+            Err(e) if mode == ParseMode::Lenient && matches!(e.kind, HandParseErrorKind::UnknownAction | HandParseErrorKind::UnseatedPlayer) => Ok(PlayerAction {
+                player_name: String::new(),
+                action: Action::Unknown { raw: line.trim_end().to_string() },
+            }),
+            result => result,
+        }
+    }
+
     fn print(&self) {
         match &self.action {
             Action::PostSmallBlind(amount) => {
@@ -286,7 +552,7 @@ impl PlayerAction {
                 println!("{}: mucks hand", self.player_name);
             },
             Action::Shows(cards, player_name) => {
-                println!("{}: shows [{}] ({})", self.player_name, cards.join(" "), player_name);
+                println!("{}: shows [{}] ({})", self.player_name, join_cards(cards), player_name);
             },
             Action::Collected(amount) => {
                 println!("{} collected ${:.2} from pot", self.player_name, amount);
@@ -315,12 +581,48 @@ impl PlayerAction {
             Action::Connected => {
                 println!("{} is connected", self.player_name);
             },
-            Action::CollectedFromSidePot(amount) => {
-                println!("{} collected ${:.2} from side pot", self.player_name, amount);
+            Action::CollectedFromSidePot(amount, number) => {
+                println!("{} collected ${:.2} from side pot-{}", self.player_name, amount, number);
             },
             Action::CollectedFromMainPot(amount) => {
                 println!("{} collected ${:.2} from main pot", self.player_name, amount);
             },
+            Action::PostSmallAndBigBlind(amount) => {
+                println!("{}: posts small & big blinds ${:.2}", self.player_name, amount);
+            },
+            Action::Chat(message) => {
+                println!("{} said, \"{}\"", self.player_name, message);
+            },
+            Action::Returned => {
+                println!("{} has returned", self.player_name);
+            },
+            Action::IsSittingOut => {
+                println!("{} is sitting out", self.player_name);
+            },
+            Action::WaitsForButton => {
+                println!("{} will be allowed to play after the button", self.player_name);
+            },
+            Action::AddsChips(amount) => {
+                println!("{} adds ${:.2}", self.player_name, amount);
+            },
+            Action::PostAnte(amount) => {
+                println!("{}: posts the ante ${:.2}", self.player_name, amount);
+            },
+            Action::PostStraddle(amount) => {
+                println!("{}: posts straddle ${:.2}", self.player_name, amount);
+            },
+            Action::FinishedTournament(place, prize) => {
+                match place {
+                    Some(place) => println!("{} finished the tournament in place {} and received ${:.2}", self.player_name, place, prize),
+                    None => println!("{} finished the tournament", self.player_name),
+                }
+            },
+            Action::WonBounty(amount, eliminated) => {
+                println!("{} wins ${:.2} for eliminating {}", self.player_name, amount, eliminated);
+            },
+            Action::Unknown { raw } => {
+                println!("{}", raw);
+            },
         }
     }
 
@@ -334,6 +636,24 @@ impl PlayerAction {
 }
 
 impl Action {
+    // Everything except table events (joins, chat, sitting out, ...) must come from a seated player
+    fn requires_seat(&self) -> bool {
+        !matches!(self,
+            Action::Join
+            | Action::Leave
+            | Action::Disconnected
+            | Action::Connected
+            | Action::TimedOut
+            | Action::SitsOut
+            | Action::Chat(_)
+            | Action::Returned
+            | Action::IsSittingOut
+            | Action::WaitsForButton
+            | Action::AddsChips(_)
+            | Action::Unknown { .. }
+        )
+    }
+
     fn to_json(&self) -> String {
         match self {
             Action::PostSmallBlind(a) => format!("{{\"type\":\"PostSmallBlind\",\"amount\":{}}}", a),
@@ -358,8 +678,19 @@ impl Action {
             Action::Leave => "{\"type\":\"Leave\"}".to_string(),
             Action::Disconnected => "{\"type\":\"Disconnected\"}".to_string(),
             Action::Connected => "{\"type\":\"Connected\"}".to_string(),
-            Action::CollectedFromSidePot(a) => format!("{{\"type\":\"CollectedFromSidePot\",\"amount\":{}}}", a),
+            Action::CollectedFromSidePot(a, number) => format!("{{\"type\":\"CollectedFromSidePot\",\"amount\":{},\"pot\":{}}}", a, number),
             Action::CollectedFromMainPot(a) => format!("{{\"type\":\"CollectedFromMainPot\",\"amount\":{}}}", a),
+            Action::PostSmallAndBigBlind(a) => format!("{{\"type\":\"PostSmallAndBigBlind\",\"amount\":{}}}", a),
+            Action::Chat(message) => format!("{{\"type\":\"Chat\",\"message\":\"{}\"}}", message.replace("\\", "\\\\").replace("\"", "\\\"")),
+            Action::Returned => "{\"type\":\"Returned\"}".to_string(),
+            Action::IsSittingOut => "{\"type\":\"IsSittingOut\"}".to_string(),
+            Action::WaitsForButton => "{\"type\":\"WaitsForButton\"}".to_string(),
+            Action::AddsChips(a) => format!("{{\"type\":\"AddsChips\",\"amount\":{}}}", a),
+            Action::PostAnte(a) => format!("{{\"type\":\"PostAnte\",\"amount\":{}}}", a),
+            Action::PostStraddle(a) => format!("{{\"type\":\"PostStraddle\",\"amount\":{}}}", a),
+            Action::FinishedTournament(place, prize) => format!("{{\"type\":\"FinishedTournament\",\"place\":{},\"prize\":{}}}", place.map_or("null".to_string(), |place| place.to_string()), prize),
+            Action::WonBounty(a, eliminated) => format!("{{\"type\":\"WonBounty\",\"amount\":{},\"eliminated\":\"{}\"}}", a, eliminated.replace("\"", "\\\"")),
+            Action::Unknown { raw } => format!("{{\"type\":\"Unknown\",\"raw\":\"{}\"}}", raw.replace("\\", "\\\\").replace("\"", "\\\"")),
         }
     }
 }
@@ -369,8 +700,13 @@ struct Hand {
     id: String,
     date: String,
     time: String,
+    time_zone: String,
+    timestamp: chrono::DateTime<chrono::Utc>, // the start of the hand, from the local time and its zone
+    language: Language,
     table_name: String,
-    small_blind: f32,
+    kind: HandKind,
+    game_info: GameInfo,
+    ante: Money,
     max_players: u32,
     dealer_seat: u32,
     players: Vec<Player>,
@@ -380,194 +716,356 @@ struct Hand {
     turn_actions: Vec<PlayerAction>,
     river_actions: Vec<PlayerAction>,
     show_down_actions: Vec<PlayerAction>,
-    hero_cards: Vec<String>,
-    hero_name: String,
-    community_cards: Vec<String>,
-    total_pot: f32,
-    main_pot: f32,
-    side_pot: f32,
-    side_pot2: f32,
-    rake: f32,
+    hero_cards: Option<Vec<Card>>, // None when hero wasn't dealt in: sitting out, or a table only observed
+    hero_name: Option<String>,
+    community_cards: Vec<Card>,
+    boards: Vec<Board>,
+    pots: Vec<Pot>,
+    total_pot: Money,
+    main_pot: Money,
+    side_pot: Money,
+    side_pot2: Money,
+    rake: Money,
+    summary: Vec<SeatSummary>,
+    unknown_lines: u32,
 }
 
 impl Hand {
-    fn from_str(hand_str: &str) -> Self {
-        println!("hand_str: {}", hand_str);
-
-        let re = regex::Regex::new(r"PokerStars Hand #(\d+):\s+.+?\(\$(\d+\.\d+)\/\$(\d+\.\d+) USD\) - (\d{4}/\d{2}/\d{2} \d{1,2}:\d{2}:\d{2}) (CET|\w{2}|ET)").unwrap();
-        let re2 = regex::Regex::new(r"Table '(.+?)' (\d+)-max Seat #(\d+) is the button").unwrap();
+    fn from_str(hand_str: &str, mode: ParseMode) -> Result<Self, HandParseError> {
+        static HEADER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"PokerStars (?:Zoom )?Hand #(\d+):\s+.+?\) - (\d{4}/\d{2}/\d{2} \d{1,2}:\d{2}:\d{2}) ([A-Z]{2,5})(?: \[(\d{4}/\d{2}/\d{2} \d{1,2}:\d{2}:\d{2}) ET\])?").unwrap());
+        static TABLE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"Table '(.+?)' (\d+)-max Seat #(\d+) is the button").unwrap());
         
-        let caps = re.captures(hand_str).expect(&format!("Could not capture hand details from string: {}", hand_str));
-        let caps2 = re2.captures(hand_str).unwrap();
+        // Localized histories are rewritten into the English layout the rest of the parser reads
+        let language = Language::detect(hand_str);
+        let english = language.to_english(hand_str);
+        let hand_str = english.as_ref();
 
+        let first_line = hand_str.trim_start().lines().next().unwrap_or("");
+        let caps = HEADER_RE.captures(first_line).ok_or_else(|| HandParseError::new(HandParseErrorKind::MissingHeader, first_line))?;
         let hand_id = caps.get(1).unwrap().as_str().to_string();
-        let small_blind = caps.get(2).unwrap().as_str().parse::<f32>().unwrap();
-        let dealer_seat = caps2.get(3).unwrap().as_str().parse::<u32>().unwrap();
-        let date = caps.get(4).unwrap().as_str().split(' ').nth(0).unwrap().to_string();
-        let time = caps.get(4).unwrap().as_str().split(' ').nth(1).unwrap().to_string();
+        let table_line = hand_str.lines().find(|line| line.starts_with("Table '")).unwrap_or("");
+        let caps2 = TABLE_RE.captures(table_line).ok_or_else(|| HandParseError::new(HandParseErrorKind::MissingTableInfo, first_line).in_hand(&hand_id))?;
+
+        let dealer_seat = caps2.get(3).unwrap().as_str().parse::<u32>().map_err(|_| HandParseError::new(HandParseErrorKind::InvalidNumber, caps2.get(0).unwrap().as_str()).in_hand(&hand_id))?;
+        let date = caps.get(2).unwrap().as_str().split(' ').nth(0).unwrap().to_string();
+        let time = caps.get(2).unwrap().as_str().split(' ').nth(1).unwrap().to_string();
+        let time_zone = caps.get(3).unwrap().as_str().to_string();
+        // Prefer the player's own zone; the bracketed ET time covers zones we have no rules for
+        let local_time = |index: usize| caps.get(index).and_then(|time| chrono::NaiveDateTime::parse_from_str(time.as_str(), "%Y/%m/%d %H:%M:%S").ok());
+        let timestamp = local_time(2).and_then(|local| timestamp::to_utc(local, &time_zone))
+            .or_else(|| local_time(4).and_then(|local| timestamp::to_utc(local, "ET")))
+            .ok_or_else(|| HandParseError::new(HandParseErrorKind::UnknownTimeZone(time_zone.clone()), first_line).in_hand(&hand_id))?;
         let table_name = caps2.get(1).unwrap().as_str().to_string();
-        let max_players = caps2.get(2).unwrap().as_str().parse::<u32>().unwrap();
+        let max_players = caps2.get(2).unwrap().as_str().parse::<u32>().map_err(|_| HandParseError::new(HandParseErrorKind::InvalidNumber, caps2.get(0).unwrap().as_str()).in_hand(&hand_id))?;
+        let game_info = GameInfo::from_header(first_line, max_players).map_err(|kind| HandParseError::new(kind, first_line).in_hand(&hand_id))?;
+        let kind = HandKind::from_header(first_line).map_err(|kind| HandParseError::new(kind, first_line).in_hand(&hand_id))?;
+
+        let in_hand = |e: HandParseError| e.in_hand(&hand_id);
 
+        let players = Self::parse_seats(hand_str).map_err(&in_hand)?;
+        let seated_names = players.iter().map(|player| player.name.clone()).collect::<Vec<String>>();
 
-        let mut preflop_actions: Vec<PlayerAction> = Vec::new();
         let mut flop_actions: Vec<PlayerAction> = Vec::new();
         let mut turn_actions: Vec<PlayerAction> = Vec::new(); 
         let mut river_actions: Vec<PlayerAction> = Vec::new();
         let mut show_down_actions: Vec<PlayerAction> = Vec::new();
 
-        let base_str = hand_str.split("*** HOLE CARDS ***") // preflop, flop, turn, river, show down
-        .nth(1)
-        .unwrap()
-        .split("*** SUMMARY ***")
-        .nth(0)
-        .unwrap();
+        let (preflop_str, line_number) = Self::section(hand_str, "*** HOLE CARDS ***", &["*** FLOP ***", "*** FIRST ", "*** SHOW DOWN ***", "*** SUMMARY ***"])
+            .ok_or_else(|| HandParseError::new(HandParseErrorKind::MissingSection("*** HOLE CARDS ***"), first_line).in_hand(&hand_id))?;
+        let preflop_actions = Self::parse_preflop_actions(preflop_str, line_number, &seated_names, mode).map_err(&in_hand)?;
 
-        if base_str.contains("*** FLOP ***") {
-            let preflop_str = base_str.split("*** FLOP ***")
-            .nth(0)
-            .unwrap();
-            preflop_actions = Self::parse_preflop_actions(preflop_str);
+        if let Some((flop_str, line_number)) = Self::section(hand_str, "*** FLOP ***", &["*** TURN ***", "*** FIRST ", "*** SHOW DOWN ***", "*** SUMMARY ***"]) {
+            flop_actions = Self::parse_flop_actions(flop_str, line_number, &seated_names, mode).map_err(&in_hand)?;
         }
 
-        if base_str.contains("*** FLOP ***") {
-            let flop_str = base_str.split("*** FLOP ***")
-            .nth(1)
-            .unwrap()
-            .split("*** TURN ***")
-            .nth(0)
-            .unwrap();
-            flop_actions = Self::parse_flop_actions(flop_str);
+        if let Some((turn_str, line_number)) = Self::section(hand_str, "*** TURN ***", &["*** RIVER ***", "*** FIRST ", "*** SHOW DOWN ***", "*** SUMMARY ***"]) {
+            turn_actions = Self::parse_turn_actions(turn_str, line_number, &seated_names, mode).map_err(&in_hand)?;
         }
 
-        if base_str.contains("*** TURN ***") {
-            let turn_str = base_str.split("*** TURN ***")
-            .nth(1)
-            .unwrap()
-            .split("*** RIVER ***")
-            .nth(0)
-            .unwrap();
-            turn_actions = Self::parse_turn_actions(turn_str);
+        if let Some((river_str, line_number)) = Self::section(hand_str, "*** RIVER ***", &["*** FIRST ", "*** SHOW DOWN ***", "*** SUMMARY ***"]) {
+            river_actions = Self::parse_river_actions(river_str, line_number, &seated_names, mode).map_err(&in_hand)?;
         }
 
-        if base_str.contains("*** RIVER ***") {
-            let river_str = base_str.split("*** RIVER ***")
-            .nth(1)
-            .unwrap()
-            .split("*** SHOW DOWN ***")
-            .nth(0)
-            .unwrap();
-            river_actions = Self::parse_river_actions(river_str);
+        if let Some((show_down_str, line_number)) = Self::section(hand_str, "*** SHOW DOWN ***", &["*** SUMMARY ***"]) {
+            show_down_actions = Self::parse_show_down_actions(show_down_str, line_number, &seated_names, mode).map_err(&in_hand)?;
         }
 
-        if base_str.contains("*** SHOW DOWN ***") {
-            let show_down_str = base_str.split("*** SHOW DOWN ***")
-            .nth(1)
-            .unwrap();
-            show_down_actions = Self::parse_show_down_actions(show_down_str);
+        // When the hand is run twice every board gets its own show down; show_down_actions holds all of them
+        // so chip counts stay right, and each board keeps the collections made on it
+        let mut show_down_actions_per_board: Vec<Vec<PlayerAction>> = Vec::new();
+        if let Some((show_down_str, line_number)) = Self::section(hand_str, "*** FIRST SHOW DOWN ***", &["*** SECOND SHOW DOWN ***", "*** SUMMARY ***"]) {
+            show_down_actions_per_board.push(Self::parse_show_down_actions(show_down_str, line_number, &seated_names, mode).map_err(&in_hand)?);
+        }
+        if let Some((show_down_str, line_number)) = Self::section(hand_str, "*** SECOND SHOW DOWN ***", &["*** SUMMARY ***"]) {
+            show_down_actions_per_board.push(Self::parse_show_down_actions(show_down_str, line_number, &seated_names, mode).map_err(&in_hand)?);
         }
+        show_down_actions.extend(show_down_actions_per_board.iter().flatten().cloned());
 
-        let pre_actions = Self::parse_pre_actions(hand_str);
-        let (hero_cards, hero_name) = Self::parse_hero_cards_and_name(hand_str);
+        let pre_actions = Self::parse_pre_actions(hand_str, &seated_names, mode).map_err(&in_hand)?;
+        let actions = HandActions { pre: &pre_actions, preflop: &preflop_actions, flop: &flop_actions, turn: &turn_actions, river: &river_actions, show_down: &show_down_actions };
+        let hero = Self::parse_hero_cards_and_name(hand_str, game_info.variant.hole_card_count()).map_err(&in_hand)?;
         
-        let community_cards = Self::parse_community_cards(hand_str);
-        let (total_pot, main_pot, side_pot, side_pot2, rake) = Self::parse_pot_and_rake(hand_str);
-        let players = Self::parse_players(hand_str, dealer_seat, &pre_actions, &preflop_actions, &flop_actions, &turn_actions, &river_actions, &show_down_actions);
+        let board_cards = Self::parse_boards(hand_str).map_err(&in_hand)?;
+        let boards = if show_down_actions_per_board.len() > 1 {
+            board_cards.into_iter().zip(show_down_actions_per_board.iter()).map(|(cards, actions)| Board { cards, winners: Self::winners(actions.iter()) }).collect::<Vec<Board>>()
+        } else {
+            board_cards.into_iter().take(1).map(|cards| Board { cards, winners: Self::winners(actions.all()) }).collect::<Vec<Board>>()
+        };
+        let community_cards = boards.first().map(|board| board.cards.clone()).unwrap_or_default();
+        Self::check_duplicate_cards(hero.as_ref(), &boards, actions.all()).map_err(&in_hand)?;
+        let (total_pot, pot_amounts, rake) = Self::parse_pot_and_rake(hand_str).map_err(&in_hand)?;
+        // The header doesn't state the ante, so take it from the largest ante posted
+        let ante = pre_actions.iter().fold(Money::ZERO, |ante, action| match action.action {
+            Action::PostAnte(amount) => Money::max(ante, amount),
+            _ => ante,
+        });
+        let mut players = Self::parse_players(players, dealer_seat, game_info.small_blind, actions);
+        let seats = players.iter().map(|player| (player.seat, player.name.clone())).collect::<Vec<_>>();
+        let summary = SeatSummary::parse_all(Self::section(hand_str, "*** SUMMARY ***", &[]).map_or("", |(summary, _)| summary), &seats);
+        Self::mark_known_cards(&mut players, hero.as_ref(), actions.all(), &summary);
+        let pots = Self::build_pots(&players, game_info.small_blind, &pot_amounts, actions);
+        // Kept for older backends that only know about two side pots
+        let pot_amount = |index: usize| pots.get(index).map_or(Money::ZERO, |pot| pot.amount);
+        let (main_pot, side_pot, side_pot2) = (pot_amount(0), pot_amount(1), pot_amount(2));
+
+        let unknown_lines = actions.all()
+            .filter(|action| matches!(action.action, Action::Unknown { .. }))
+            .count() as u32;
         
-        Hand {
+        Ok(Hand {
             id: hand_id,
-            small_blind,
+            kind,
+            game_info,
+            ante,
             date,
             time,
+            time_zone,
+            timestamp,
+            language,
             table_name,
             max_players,
             dealer_seat,
-            hero_name: hero_name.to_string(),
+            hero_name: hero.as_ref().map(|(_, hero_name)| hero_name.clone()),
             players,
             pre_actions,
-            hero_cards,
+            hero_cards: hero.map(|(hero_cards, _)| hero_cards),
             preflop_actions,
             flop_actions,
             turn_actions,
             river_actions,
             show_down_actions,
             community_cards,
+            boards,
+            pots,
             total_pot,
             main_pot,
             side_pot,
             side_pot2,
             rake,
-        }
+            summary,
+            unknown_lines,
+        })
     }
 
-    fn parse_players(hand_str: &str, dealer_seat: u32, pre_actions: &Vec<PlayerAction>, preflop_actions: &Vec<PlayerAction>, flop_actions: &Vec<PlayerAction>, turn_actions: &Vec<PlayerAction>, river_actions: &Vec<PlayerAction>, show_down_actions: &Vec<PlayerAction>) -> Vec<Player> {
+    // Returns the text between `start` and the first of `ends` that follows it,
+    // together with the 1-based line number (within the hand) the section starts on
+    fn section<'a>(hand_str: &'a str, start: &str, ends: &[&str]) -> Option<(&'a str, usize)> {
+        let start_index = hand_str.find(start)? + start.len();
+        let rest = &hand_str[start_index..];
+        let end_index = ends.iter().filter_map(|end| rest.find(end)).min().unwrap_or(rest.len());
+        let line_number = hand_str[..start_index].lines().count();
+        Some((&rest[..end_index], line_number))
+    }
+
+    fn parse_seats(hand_str: &str) -> Result<Vec<Player>, HandParseError> {
         let mut players = Vec::new();
         
         // Regex to match seat information
-        let seat_re = Regex::new(r"Seat (\d+): (.+?) \(\$([0-9.]+) in chips\)( is sitting out)?").unwrap();
+        static SEAT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"Seat (\d+): (.+?) \({amount} in chips(?:, {amount} bounty)?\)( is sitting out| out of hand)?")).unwrap());
         
-        for line in hand_str.lines() {
-            if let Some(caps) = seat_re.captures(line) {
-                let seat = caps.get(1).unwrap().as_str().parse::<u32>().unwrap();
+        for line in hand_str.lines().filter(|line| line.starts_with("Seat ")) {
+            if let Some(caps) = SEAT_RE.captures(line) {
+                let seat = caps.get(1).unwrap().as_str().parse::<u32>().map_err(|_| HandParseError::new(HandParseErrorKind::InvalidNumber, line))?;
                 let name = caps.get(2).unwrap().as_str().to_string();
-                let chips = caps.get(3).unwrap().as_str().parse::<f32>().unwrap();
-                let is_sitting_out = caps.get(4).is_some();
+                let chips = parse_amount(&caps, 3).ok_or_else(|| HandParseError::new(HandParseErrorKind::InvalidNumber, line))?;
+                let is_sitting_out = caps.get(5).is_some(); // group 4 is the knockout bounty
 
                 players.push(Player {
                     seat,
                     position: Option::<Position>::None,
-                    name: name.clone(),
+                    name,
                     chips,
-                    chips_after_hand: Self::calculate_chips_after_hand(name.clone(), chips, pre_actions, preflop_actions, flop_actions, turn_actions, river_actions, show_down_actions),
+                    chips_after_hand: chips,
                     is_sitting_out,
+                    is_hero: false,
+                    hole_cards: None,
+                    hand_description: None,
                 });
             }
         }
-        
-        let playing_players_count = players.iter().filter(|p| !p.is_sitting_out).count();
-        let players_count = players.len() as u32;
-        players.sort_by_key(|p| if p.is_sitting_out { players_count + 1 } else { (p.seat as i32 - dealer_seat as i32 + players_count as i32) as u32 % players_count });
-    
-        for index in 0..playing_players_count {
-            // IF BTN, SB, BB, then the position is the index
-            // IF index is 3, 4, 5,..., then calculate your effective position based on how many players are sitting out
-            players[index].position = Some(Position::from_index(if index < 3 { index } else { index+(9-playing_players_count) }));
+
+        Ok(players)
+    }
+
+    // In pot-limit games a bet can be at most the pot, and a raise at most the pot after calling
+    fn check_pot_limit(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let small_blind = self.game_info.small_blind;
+        let mut pot = Money::ZERO;
+        let mut street_bets: HashMap<&str, Money> = HashMap::new();
+
+        for action in &self.pre_actions {
+            let name = action.player_name.as_str();
+            match action.action {
+                Action::PostSmallBlind(amount) | Action::PostBigBlind(amount) | Action::PostStraddle(amount) => *street_bets.entry(name).or_default() += amount,
+                Action::PostSmallAndBigBlind(amount) => {
+                    *street_bets.entry(name).or_default() += amount - small_blind;
+                    pot += small_blind;
+                },
+                Action::PostAnte(amount) => pot += amount,
+                _ => {}
+            }
         }
 
+        let streets = [(Street::Preflop, &self.preflop_actions), (Street::Flop, &self.flop_actions), (Street::Turn, &self.turn_actions), (Street::River, &self.river_actions)];
+        for (street, actions) in streets {
+            if street != Street::Preflop {
+                pot += street_bets.values().sum::<Money>();
+                street_bets.clear();
+            }
+            for action in actions.iter() {
+                let name = action.player_name.as_str();
+                let current_bet = street_bets.values().copied().max().unwrap_or(Money::ZERO);
+                let player_bet = street_bets.get(name).copied().unwrap_or(Money::ZERO);
+                let to = match action.action {
+                    Action::Bet(amount) | Action::BetAndAllIn(amount) => player_bet + amount,
+                    Action::Raise(_, to) | Action::RaiseAndAllIn(_, to) => to,
+                    Action::Call(amount) | Action::CallAndAllIn(amount) => player_bet + amount,
+                    Action::UncalledBet(amount) => player_bet - amount,
+                    _ => continue,
+                };
+                if matches!(action.action, Action::Bet(_) | Action::BetAndAllIn(_) | Action::Raise(..) | Action::RaiseAndAllIn(..)) {
+                    let pot_limit = current_bet + pot + street_bets.values().sum::<Money>() + (current_bet - player_bet);
+                    if to > pot_limit {
+                        issues.push(ValidationIssue::PotLimit { player: name.to_string(), street: street.as_str().to_string(), to, pot_limit });
+                    }
+                }
+                street_bets.insert(name, to);
+            }
+        }
+        issues
+    }
+
+    fn parse_players(mut players: Vec<Player>, dealer_seat: u32, small_blind: Money, actions: HandActions) -> Vec<Player> {
+        for player in players.iter_mut() {
+            player.chips_after_hand = Self::calculate_chips_after_hand(player.name.clone(), player.chips, small_blind, actions);
+        }
+        
+        // A straddler keeps their seat's position; the straddle itself is their PostStraddle action
+        Self::assign_positions(&mut players, dealer_seat, actions.pre);
+
         // Sort by seat number
         players.sort_by_key(|p| p.seat);
         players
     }
 
-    fn calculate_chips_after_hand(name: String, chips: f32, pre_actions: &Vec<PlayerAction>, preflop_actions: &Vec<PlayerAction>, flop_actions: &Vec<PlayerAction>, turn_actions: &Vec<PlayerAction>, river_actions: &Vec<PlayerAction>, show_down_actions: &Vec<PlayerAction>) -> f32 {
-        let mut chips_after_hand = chips;
+    // Positions follow the blinds actually posted: the small and big blind posters are SB and BB, the player on
+    // the button is BTN and everyone else is labelled by how many players are dealt in. Heads-up the button
+    // posts the small blind, so the two players are SB and BB. Players sitting out get no position
+    fn assign_positions(players: &mut [Player], dealer_seat: u32, pre_actions: &[PlayerAction]) {
+        let last_seat = players.iter().map(|p| p.seat).max().unwrap_or(0).max(dealer_seat);
+        // Clockwise from the seat after the button, so the button (or the seat before an empty button) is last
+        let mut dealt_in: Vec<usize> = (0..players.len()).filter(|&index| !players[index].is_sitting_out).collect();
+        if dealt_in.len() < 2 {
+            return;
+        }
+        dealt_in.sort_by_key(|&index| (players[index].seat + last_seat - dealer_seat - 1) % last_seat);
+
+        let poster = |is_blind: fn(&Action) -> bool| pre_actions.iter()
+            .find(|action| is_blind(&action.action))
+            .and_then(|action| dealt_in.iter().copied().find(|&index| players[index].name == action.player_name));
+        let posted_big_blind = poster(|action| matches!(action, Action::PostBigBlind(_)));
+        let posted_small_blind = poster(|action| matches!(action, Action::PostSmallBlind(_)));
+        // Without a big blind poster (a history cut short) fall back to the seats after the button
+        let (small_blind, big_blind) = match posted_big_blind {
+            Some(big_blind) => (posted_small_blind, big_blind),
+            None if dealt_in.len() == 2 => (Some(dealt_in[1]), dealt_in[0]),
+            None => (Some(dealt_in[0]), dealt_in[1]),
+        };
+        let button = dealt_in.iter().copied().find(|&index| players[index].seat == dealer_seat && index != big_blind && Some(index) != small_blind);
+
+        // Everyone else in the order they act preflop: from the seat after the big blind around to the button
+        let big_blind_at = dealt_in.iter().position(|&index| index == big_blind).unwrap_or(0);
+        let others: Vec<usize> = dealt_in[big_blind_at + 1..].iter().chain(dealt_in[..big_blind_at].iter())
+            .copied()
+            .filter(|&index| index != big_blind && Some(index) != small_blind && Some(index) != button)
+            .collect();
+
+        for (index, position) in others.iter().zip(Position::before_button(others.len())) {
+            players[*index].position = Some(position);
+        }
+        if let Some(button) = button {
+            players[button].position = Some(Position::BTN);
+        }
+        if let Some(small_blind) = small_blind {
+            players[small_blind].position = Some(Position::SB);
+        }
+        players[big_blind].position = Some(Position::BB);
+    }
+
+    fn calculate_chips_after_hand(name: String, chips: Money, small_blind: Money, actions: HandActions) -> Money {
+        let mut chips_after_hand = chips - Self::calculate_contribution(&name, small_blind, actions);
+
+        for action in actions.all() {
+            if action.player_name == name {
+                match &action.action {
+                    &Action::Collected(amount) => chips_after_hand += amount,
+                    &Action::CollectedFromSidePot(amount, _) => chips_after_hand += amount,
+                    &Action::CollectedFromMainPot(amount) => chips_after_hand += amount,
+                    &Action::CashedOut(amount, _) => chips_after_hand += amount,
+                    &Action::AddsChips(amount) => chips_after_hand += amount,
+                    _ => {}
+                }
+            }
+        }
+
+        chips_after_hand
+    }
+
+    // Everything the player put into the pot, less any uncalled bet returned to them
+    fn calculate_contribution(name: &str, small_blind: Money, actions: HandActions) -> Money {
+        let mut contribution = Money::ZERO;
 
-        let mut put_into_pot = 0.0;
+        let mut put_into_pot = Money::ZERO;
+        // Antes and the small blind part of "posts small & big blinds" are dead money: they don't count towards calls and raises
+        let mut dead_money = Money::ZERO;
 
-        for action in pre_actions.iter().chain(preflop_actions.iter()) {
+        for action in actions.pre.iter().chain(actions.preflop) {
             if action.player_name == name {
                 match &action.action {
                     &Action::PostSmallBlind(amount) => put_into_pot += amount,
                     &Action::PostBigBlind(amount) => put_into_pot += amount,
+                    &Action::PostSmallAndBigBlind(amount) => {
+                        put_into_pot += amount - small_blind;
+                        dead_money += small_blind;
+                    },
+                    &Action::PostAnte(amount) => dead_money += amount,
+                    &Action::PostStraddle(amount) => put_into_pot += amount,
                     &Action::Bet(amount) => put_into_pot += amount,
                     &Action::BetAndAllIn(amount) => put_into_pot += amount,
                     &Action::Raise(_, to) => put_into_pot += to - put_into_pot,
                     &Action::RaiseAndAllIn(_, to) => put_into_pot += to - put_into_pot,
                     &Action::Call(amount) => put_into_pot += amount,
                     &Action::CallAndAllIn(amount) => put_into_pot += amount,
-                    &Action::Collected(amount) => chips_after_hand += amount,
-                    &Action::CollectedFromSidePot(amount) => chips_after_hand += amount,
-                    &Action::CollectedFromMainPot(amount) => chips_after_hand += amount,
-                    &Action::CashedOut(amount, _) => chips_after_hand += amount,
-                    &Action::UncalledBet(amount) => chips_after_hand += amount,
+                    &Action::UncalledBet(amount) => contribution -= amount,
                     _ => {}
                 }
             }
         }
-        chips_after_hand -= put_into_pot;
+        contribution += put_into_pot + dead_money;
 
-        let streets = vec![flop_actions, turn_actions, river_actions, show_down_actions];
-        for street in streets {
-            put_into_pot = 0.0;
+        for street in [actions.flop, actions.turn, actions.river, actions.show_down] {
+            put_into_pot = Money::ZERO;
             for action in street {
                 if action.player_name == name {
                     match &action.action {
@@ -577,176 +1075,484 @@ impl Hand {
                         &Action::RaiseAndAllIn(_, to) => put_into_pot += to - put_into_pot,
                         &Action::Call(amount) => put_into_pot += amount,
                         &Action::CallAndAllIn(amount) => put_into_pot += amount,
-                        &Action::Collected(amount) => chips_after_hand += amount,
-                        &Action::CollectedFromSidePot(amount) => chips_after_hand += amount,
-                        &Action::CollectedFromMainPot(amount) => chips_after_hand += amount,
-                        &Action::CashedOut(amount, _) => chips_after_hand += amount,
-                        &Action::UncalledBet(amount) => chips_after_hand += amount,
+                        &Action::UncalledBet(amount) => contribution -= amount,
                         _ => {}
                     }
                 }
             }
-            chips_after_hand -= put_into_pot;
+            contribution += put_into_pot;
         }
 
-        let rounded = (chips_after_hand * 100.0).round() / 100.0;
-        rounded as f32
+        contribution
     }
 
-    fn parse_pre_actions(hand_str: &str) -> Vec<PlayerAction> {
+    fn parse_pre_actions(hand_str: &str, seated_names: &[String], mode: ParseMode) -> Result<Vec<PlayerAction>, HandParseError> {
         let mut pre_actions = Vec::new();
         
-        // Regex patterns for different actions
-        let small_blind_re = Regex::new(r"(.+?): posts small blind \$([0-9.]+)").unwrap();
-        let big_blind_re = Regex::new(r"(.+?): posts big blind \$([0-9.]+)").unwrap();
-        let sits_out_re = Regex::new(r"(.+?): sits out").unwrap();
+        // Everything between the seat list and "*** HOLE CARDS ***": blinds, sit outs, table management
+        let pre_str = hand_str.split("*** HOLE CARDS ***").next().unwrap_or("");
+        let pre_lines = pre_str.lines().enumerate().filter(|(_, line)| !line.is_empty() && !line.starts_with("PokerStars ") && !line.starts_with("Table '") && !line.starts_with("Seat "));
+        for (index, line) in pre_lines {
+            let action = PlayerAction::from_str_with_mode(line, seated_names, mode).map_err(|e| e.at(Street::Pre, index + 1))?;
+            pre_actions.push(action);
+        }
         
-        for line in hand_str.lines() {
-            if let Some(caps) = small_blind_re.captures(line) {
-                let player_name = caps.get(1).unwrap().as_str().to_string();
-                let amount = caps.get(2).unwrap().as_str().parse::<f32>().unwrap();
-                pre_actions.push(PlayerAction {
-                    player_name,
-                    action: Action::PostSmallBlind(amount),
-                });
-            } else if let Some(caps) = big_blind_re.captures(line) {
-                let player_name = caps.get(1).unwrap().as_str().to_string();
-                let amount = caps.get(2).unwrap().as_str().parse::<f32>().unwrap();
-                pre_actions.push(PlayerAction {
-                    player_name,
-                    action: Action::PostBigBlind(amount),
-                });
-            } else if let Some(caps) = sits_out_re.captures(line) {
-                let player_name = caps.get(1).unwrap().as_str().to_string();
-                pre_actions.push(PlayerAction {
-                    player_name,
-                    action: Action::SitsOut,
-                });
+        Ok(pre_actions)
+    }
+
+    // Collects every card we get to see per player: hero's from "Dealt to", the showdown's "shows" and the summary's
+    // "showed"/"mucked" lines. A player who only showed one card keeps the longer list when both are known
+    fn mark_known_cards<'a>(players: &mut [Player], hero: Option<&(Vec<Card>, String)>, actions: impl Iterator<Item = &'a PlayerAction>, summary: &[SeatSummary]) {
+        let mut known = |name: &str, cards: Vec<Card>, description: Option<&str>| {
+            if let Some(player) = players.iter_mut().find(|player| player.name == name) {
+                if player.hole_cards.as_ref().is_none_or(|known| known.len() < cards.len()) {
+                    player.hole_cards = Some(cards);
+                }
+                if player.hand_description.is_none() {
+                    player.hand_description = description.filter(|description| !description.is_empty()).map(|description| description.to_string());
+                }
             }
+        };
+
+        if let Some((hero_cards, hero_name)) = hero {
+            known(hero_name, hero_cards.clone(), None);
+        }
+        for action in actions {
+            if let Action::Shows(cards, description) = &action.action {
+                known(&action.player_name, cards.clone(), Some(description));
+            }
+        }
+        for seat in summary {
+            match &seat.outcome {
+                SeatOutcome::Showed { cards, description, .. } => known(&seat.name, cards.clone(), description.as_deref()),
+                SeatOutcome::Mucked { cards: Some(cards) } => known(&seat.name, cards.clone(), None),
+                _ => {}
+            }
+        }
+
+        for player in players.iter_mut() {
+            player.is_hero = hero.is_some_and(|(_, hero_name)| player.name == *hero_name);
         }
-        
-        pre_actions
     }
 
-    fn parse_hero_cards_and_name(hand_str: &str) -> (Vec<String>, String) {
-        let playerhands_re = Regex::new(r"Dealt to (.+?) \[([2-9TJQKA][cdhs]) ([2-9TJQKA][cdhs])\]").unwrap();
-        let playerhands_caps = playerhands_re.captures(hand_str).unwrap();
+    // Hold'em deals 2 hole cards, Omaha 4 to 6
+    // Hero's cards and name from the "Dealt to" line, or None when there is none: hero was sitting out,
+    // or the history is of a table the user only observed
+    fn parse_hero_cards_and_name(hand_str: &str, hole_card_count: usize) -> Result<Option<(Vec<Card>, String)>, HandParseError> {
+        static PLAYERHANDS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"Dealt to (.+?) \[((?:[2-9TJQKA][cdhs] ?)+)\]").unwrap());
+        let Some(line) = hand_str.lines().find(|line| line.starts_with("Dealt to")) else {
+            return Ok(None);
+        };
+        let playerhands_caps = PLAYERHANDS_RE.captures(line).ok_or_else(|| HandParseError::new(HandParseErrorKind::MissingHeroCards, line))?;
         let hero_name = playerhands_caps.get(1).unwrap().as_str().to_string();
-        let hero_cards = vec![playerhands_caps.get(2).unwrap().as_str().to_string(), playerhands_caps.get(3).unwrap().as_str().to_string()];
-        (hero_cards, hero_name)
+        let hero_cards = parse_cards(playerhands_caps.get(2).unwrap().as_str())
+            .filter(|cards| cards.len() == hole_card_count)
+            .ok_or_else(|| HandParseError::new(HandParseErrorKind::MissingHeroCards, line))?;
+        Ok(Some((hero_cards, hero_name)))
     }
 
-    fn parse_community_cards(hand_str: &str) -> Vec<String> {
-        let mut community_cards = Vec::new();
-        
-        if !hand_str.contains("*** FLOP ***") {
-            return community_cards;
+    // Without a "Dealt to" line hero may still be seated under one of the user's screen names, e.g. while sitting out
+    fn identify_hero(&mut self, screen_names: &[String]) {
+        if self.hero_name.is_some() {
+            return;
         }
+        if let Some(player) = self.players.iter_mut().find(|player| screen_names.contains(&player.name)) {
+            player.is_hero = true;
+            self.hero_name = Some(player.name.clone());
+        }
+    }
 
-        let community_cards_re = Regex::new(r"Board \[([2-9TJQKA][cdhs]) ([2-9TJQKA][cdhs]) ([2-9TJQKA][cdhs])( ([2-9TJQKA][cdhs]))??( ([2-9TJQKA][cdhs]))?\]").unwrap();
-        let community_cards_caps = community_cards_re.captures(hand_str).unwrap();
-        for cap in community_cards_caps.iter() {
-            if cap.is_some() {
-                if cap.unwrap().as_str().len() == 2 {
-                    community_cards.push(cap.unwrap().as_str().to_string());
+    // A hand with no hero at the table was only watched
+    fn is_observed(&self) -> bool {
+        self.hero_name.is_none()
+    }
+
+    // No card may be dealt twice: hole cards (hero's and any shown), and every board.
+    // A player's shows only reveal their own hole cards, so a partial show, a reordered show or showing again on
+    // the second board of a run-it-twice hand adds nothing new; only cards held by two players or by a player and
+    // the board collide. Boards of a run-it-twice hand share the cards dealt before the all-in, so only the cards
+    // that differ are new
+    fn check_duplicate_cards<'a>(hero: Option<&(Vec<Card>, String)>, boards: &[Board], actions: impl Iterator<Item = &'a PlayerAction>) -> Result<(), HandParseError> {
+        let mut hole_cards: Vec<(&str, Vec<Card>)> = hero.iter().map(|(hero_cards, hero_name)| (hero_name.as_str(), hero_cards.clone())).collect();
+        for action in actions {
+            if let Action::Shows(cards, _) = &action.action {
+                let known = match hole_cards.iter().position(|(name, _)| *name == action.player_name) {
+                    Some(index) => &mut hole_cards[index].1,
+                    None => {
+                        hole_cards.push((&action.player_name, Vec::new()));
+                        &mut hole_cards.last_mut().unwrap().1
+                    },
+                };
+                for card in cards {
+                    if !known.contains(card) {
+                        known.push(*card);
+                    }
                 }
             }
         }
-        community_cards
+
+        let mut dealt_cards = hole_cards.iter().flat_map(|(_, cards)| cards.iter()).collect::<Vec<&Card>>();
+        if let Some((first_board, other_boards)) = boards.split_first() {
+            dealt_cards.extend(first_board.cards.iter());
+            for board in other_boards {
+                dealt_cards.extend(board.cards.iter().enumerate().filter(|(index, card)| first_board.cards.get(*index) != Some(*card)).map(|(_, card)| card));
+            }
+        }
+
+        let mut seen: u64 = 0;
+        for card in dealt_cards {
+            if seen & card.bit() != 0 {
+                let line = format!("{} appears more than once", card);
+                return Err(HandParseError::new(HandParseErrorKind::DuplicateCard(card.to_string()), &line));
+            }
+            seen |= card.bit();
+        }
+        Ok(())
+    }
+
+    // Reads "Board [..]" from the summary, or "FIRST Board [..]" and "SECOND Board [..]" when the hand was run twice
+    fn parse_boards(hand_str: &str) -> Result<Vec<Vec<Card>>, HandParseError> {
+        static BOARD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:(?:FIRST|SECOND) )?Board \[((?:[2-9TJQKA][cdhs] ?){3,5})\]").unwrap());
+
+        let boards = hand_str.lines()
+            .filter_map(|line| BOARD_RE.captures(line))
+            .filter_map(|caps| parse_cards(caps.get(1).unwrap().as_str()))
+            .collect::<Vec<_>>();
+
+        if boards.is_empty() && (hand_str.contains("*** FLOP ***") || hand_str.contains("*** FIRST FLOP ***")) {
+            let line = hand_str.lines().find(|line| line.contains("Board")).unwrap_or("");
+            return Err(HandParseError::new(HandParseErrorKind::MissingBoard, line));
+        }
+        Ok(boards)
+    }
+
+    // Sums what every player collected, in order of first collection
+    fn winners<'a>(actions: impl Iterator<Item = &'a PlayerAction>) -> Vec<(String, Money)> {
+        let mut winners: Vec<(String, Money)> = Vec::new();
+        for action in actions {
+            let amount = match action.action {
+                Action::Collected(amount) | Action::CollectedFromMainPot(amount) | Action::CollectedFromSidePot(amount, _) | Action::CashedOut(amount, _) => amount,
+                _ => continue,
+            };
+            match winners.iter_mut().find(|(name, _)| *name == action.player_name) {
+                Some((_, total)) => *total += amount,
+                None => winners.push((action.player_name.clone(), amount)),
+            }
+        }
+        winners
     }
     
-    fn parse_preflop_actions(preflop_str: &str) -> Vec<PlayerAction> {
+    fn parse_preflop_actions(preflop_str: &str, first_line: usize, seated_names: &[String], mode: ParseMode) -> Result<Vec<PlayerAction>, HandParseError> {
         let mut preflop_actions = Vec::new();
         
-        let hole_cards_lines = preflop_str.lines().filter(|line| !line.is_empty() && !line.starts_with("Dealt to") && !line.starts_with("\r\n")); // skip "Dealt to ... [Ah As]" line
-        for line in hole_cards_lines {
-            if let Some(action) = PlayerAction::from_str(line) {
-                preflop_actions.push(action);
-            }
+        let hole_cards_lines = preflop_str.lines().enumerate().filter(|(_, line)| !line.is_empty() && !line.starts_with("Dealt to")); // skip "Dealt to ... [Ah As]" line
+        for (index, line) in hole_cards_lines {
+            let action = PlayerAction::from_str_with_mode(line, seated_names, mode).map_err(|e| e.at(Street::Preflop, first_line + index))?;
+            preflop_actions.push(action);
         }
 
-        preflop_actions
+        Ok(preflop_actions)
     }
 
-    fn parse_flop_actions(flop_str: &str) -> (Vec<PlayerAction>) {
+    fn parse_flop_actions(flop_str: &str, first_line: usize, seated_names: &[String], mode: ParseMode) -> Result<Vec<PlayerAction>, HandParseError> {
         let mut flop_actions = Vec::new();
 
-        let hole_cards_lines = flop_str.lines().filter(|line| !line.is_empty() && !line.starts_with(" [") && !line.starts_with("\r\n")); // skip "*** Flop [Ah As]" line
-        for line in hole_cards_lines {
-            if let Some(action) = PlayerAction::from_str(line) {
-                flop_actions.push(action);
-            }
+        let hole_cards_lines = flop_str.lines().enumerate().filter(|(_, line)| !line.is_empty() && !line.starts_with(" [")); // skip "*** Flop [Ah As]" line
+        for (index, line) in hole_cards_lines {
+            let action = PlayerAction::from_str_with_mode(line, seated_names, mode).map_err(|e| e.at(Street::Flop, first_line + index))?;
+            flop_actions.push(action);
         } 
 
-        flop_actions
+        Ok(flop_actions)
     }
 
-    fn parse_turn_actions(turn_str: &str) -> Vec<PlayerAction> {
+    fn parse_turn_actions(turn_str: &str, first_line: usize, seated_names: &[String], mode: ParseMode) -> Result<Vec<PlayerAction>, HandParseError> {
         let mut turn_actions = Vec::new();
         
-        let hole_cards_lines = turn_str.lines().filter(|line| !line.is_empty() && !line.starts_with(" [") && !line.starts_with("\r\n")); // skip "*** Flop [Ah As]" line
-        for line in hole_cards_lines {
-            if let Some(action) = PlayerAction::from_str(line) {
-                turn_actions.push(action);
-            }
+        let hole_cards_lines = turn_str.lines().enumerate().filter(|(_, line)| !line.is_empty() && !line.starts_with(" [")); // skip "*** Flop [Ah As]" line
+        for (index, line) in hole_cards_lines {
+            let action = PlayerAction::from_str_with_mode(line, seated_names, mode).map_err(|e| e.at(Street::Turn, first_line + index))?;
+            turn_actions.push(action);
         } 
 
-        turn_actions
+        Ok(turn_actions)
     }
 
-    fn parse_river_actions(river_str: &str) -> Vec<PlayerAction> {
+    fn parse_river_actions(river_str: &str, first_line: usize, seated_names: &[String], mode: ParseMode) -> Result<Vec<PlayerAction>, HandParseError> {
         let mut river_actions = Vec::new();
         
-        let hole_cards_lines = river_str.lines().filter(|line| !line.is_empty() && !line.starts_with(" [") && !line.starts_with("\r\n")); // skip "*** River [Ah As]" line
-        for line in hole_cards_lines {
-            if let Some(action) = PlayerAction::from_str(line) {
-                river_actions.push(action);
-            }
+        let hole_cards_lines = river_str.lines().enumerate().filter(|(_, line)| !line.is_empty() && !line.starts_with(" [")); // skip "*** River [Ah As]" line
+        for (index, line) in hole_cards_lines {
+            let action = PlayerAction::from_str_with_mode(line, seated_names, mode).map_err(|e| e.at(Street::River, first_line + index))?;
+            river_actions.push(action);
         }
 
-        river_actions
+        Ok(river_actions)
     }
 
-    fn parse_show_down_actions(show_down_str: &str) -> Vec<PlayerAction> {
+    fn parse_show_down_actions(show_down_str: &str, first_line: usize, seated_names: &[String], mode: ParseMode) -> Result<Vec<PlayerAction>, HandParseError> {
         let mut show_down_actions = Vec::new();
         
-        let hole_cards_lines = show_down_str.lines().filter(|line| !line.is_empty() && !line.starts_with("\r\n"));
-        for line in hole_cards_lines {
-            if let Some(action) = PlayerAction::from_str(line) {
-                show_down_actions.push(action);
+        let hole_cards_lines = show_down_str.lines().enumerate().filter(|(_, line)| !line.is_empty());
+        for (index, line) in hole_cards_lines {
+            let action = PlayerAction::from_str_with_mode(line, seated_names, mode).map_err(|e| e.at(Street::ShowDown, first_line + index))?;
+            show_down_actions.push(action);
+        }
+
+        Ok(show_down_actions)
+    }
+
+    // Reads "Total pot $11.59 Main pot $6.31. Side pot-1 $3.28. Side pot-2 $2. | Rake $0.31";
+    // returns the total pot, every pot amount listed (just the total when there are no side pots) and the rake
+    fn parse_pot_and_rake(hand_str: &str) -> Result<(Money, Vec<Money>, Money), HandParseError> {
+        static POT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"Total pot {amount}(.*?)\| Rake {amount}")).unwrap());
+        static SIDE_POT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"(?:Main pot|Side pot(?:-\d+)?) {amount}\.")).unwrap());
+        let pot_line = hand_str.lines().find(|line| line.starts_with("Total pot")).unwrap_or("");
+        let pot_re_caps = POT_RE.captures(hand_str).ok_or_else(|| HandParseError::new(HandParseErrorKind::MissingPot, pot_line))?;
+        let invalid_number = || HandParseError::new(HandParseErrorKind::InvalidNumber, pot_line);
+
+        let total_pot = parse_amount(&pot_re_caps, 1).ok_or_else(invalid_number)?;
+        let rake = parse_amount(&pot_re_caps, 3).ok_or_else(invalid_number)?;
+
+        let mut pot_amounts = Vec::new();
+        for caps in SIDE_POT_RE.captures_iter(pot_re_caps.get(2).unwrap().as_str()) {
+            pot_amounts.push(parse_amount(&caps, 1).ok_or_else(invalid_number)?);
+        }
+        if pot_amounts.is_empty() {
+            pot_amounts.push(total_pot);
+        }
+
+        Ok((total_pot, pot_amounts, rake))
+    }
+
+    // Replays every player's contribution to split the pot into a main pot and side pots:
+    // each all-in amount caps a pot that only the players who matched it can win
+    fn build_pots(players: &[Player], small_blind: Money, pot_amounts: &[Money], actions: HandActions) -> Vec<Pot> {
+        let all_actions = || actions.all();
+
+        let contributions = players.iter()
+            .map(|player| (player.name.as_str(), Self::calculate_contribution(&player.name, small_blind, actions)))
+            .filter(|(_, contribution)| *contribution > Money::ZERO)
+            .collect::<Vec<_>>();
+        let folded = |name: &str| all_actions().any(|action| action.player_name == name && matches!(action.action, Action::Fold));
+
+        let mut levels = contributions.iter().filter(|(name, _)| !folded(name)).map(|(_, contribution)| *contribution).collect::<Vec<Money>>();
+        levels.sort();
+        levels.dedup();
+
+        let mut pots = Vec::new();
+        let mut previous_level = Money::ZERO;
+        for level in levels {
+            pots.push(Pot {
+                amount: contributions.iter().map(|(_, contribution)| Money::min(*contribution, level) - Money::min(*contribution, previous_level)).sum(),
+                eligible_players: contributions.iter().filter(|(name, contribution)| !folded(name) && *contribution >= level).map(|(name, _)| name.to_string()).collect(),
+                winners: Vec::new(),
+            });
+            previous_level = level;
+        }
+
+        // The summary line is authoritative for the amounts, the replay for who could win each pot
+        for (index, amount) in pot_amounts.iter().enumerate() {
+            match pots.get_mut(index) {
+                Some(pot) => pot.amount = *amount,
+                None => pots.push(Pot { amount: *amount, eligible_players: Vec::new(), winners: Vec::new() }),
+            }
+        }
+        pots.truncate(pot_amounts.len());
+
+        for action in all_actions() {
+            let (index, amount) = match action.action {
+                Action::Collected(amount) | Action::CollectedFromMainPot(amount) | Action::CashedOut(amount, _) => (0, amount),
+                Action::CollectedFromSidePot(amount, number) => (number as usize, amount),
+                _ => continue,
+            };
+            if let Some(pot) = pots.get_mut(index) {
+                match pot.winners.iter_mut().find(|(name, _)| *name == action.player_name) {
+                    Some((_, total)) => *total += amount,
+                    None => pot.winners.push((action.player_name.clone(), amount)),
+                }
             }
         }
 
-        show_down_actions
+        pots
+    }
+
+    fn actions(&self) -> HandActions<'_> {
+        HandActions {
+            pre: &self.pre_actions,
+            preflop: &self.preflop_actions,
+            flop: &self.flop_actions,
+            turn: &self.turn_actions,
+            river: &self.river_actions,
+            show_down: &self.show_down_actions,
+        }
     }
 
-    fn parse_pot_and_rake(hand_str: &str) -> (f32, f32, f32, f32, f32) {
-        let pot_re = Regex::new(r"Total pot \$([0-9.]+)(?:\s+Main pot \$([0-9.]+)\.\s+Side pot \$([0-9.]+)\.)? \| Rake \$([0-9.]+)").unwrap();
-        let pot_re_caps = pot_re.captures(hand_str).unwrap();
+    // The table the hand belongs to for grouping: the Zoom pool, or the table itself
+    fn logical_table(&self) -> &str {
+        self.game_info.pool.as_deref().unwrap_or(&self.table_name)
+    }
 
-        let total_pot = pot_re_caps.get(1).unwrap().as_str().parse::<f32>().unwrap();
-        let rake = pot_re_caps.get(4).unwrap().as_str().parse::<f32>().unwrap();
+    // Checks that the hand adds up: contributions against the total pot, uncalled bets against the bets they
+    // exceeded, pot-limit bets against the pot, side pots and collections against the pot and rake, and stacks
+    // against the next hand at the table
+    fn validate(&self, next_hand: Option<&Hand>) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let small_blind = self.game_info.small_blind;
+
+        let contributed: Money = self.players.iter()
+            .map(|player| Self::calculate_contribution(&player.name, small_blind, self.actions()))
+            .sum();
+        if contributed != self.total_pot {
+            issues.push(ValidationIssue::Contributions { contributed, total_pot: self.total_pot });
+        }
 
-        let (main_pot, side_pot, side_pot2) = if let Some(main_pot_cap) = pot_re_caps.get(2) {
-            let main_pot = main_pot_cap.as_str().parse::<f32>().unwrap();
-            let side_pot = pot_re_caps.get(3).map(|cap| cap.as_str().trim_end_matches('.').parse::<f32>().unwrap());
-            let side_pot2: Option<f32> = Some(0.0);
-            (main_pot, side_pot.unwrap_or(0.0), side_pot2.unwrap_or(0.0))
-        } else {
-            return (total_pot, total_pot, 0.0, 0.0, rake);
-        };
+        // An uncalled bet is what the bettor put in on that street beyond the largest amount anyone else matched
+        let streets = [&self.preflop_actions, &self.flop_actions, &self.turn_actions, &self.river_actions];
+        for (index, actions) in streets.iter().enumerate() {
+            let mut street_bets: HashMap<&str, Money> = HashMap::new();
+            if index == 0 {
+                for action in &self.pre_actions {
+                    match action.action {
+                        Action::PostSmallBlind(amount) | Action::PostBigBlind(amount) | Action::PostStraddle(amount) => *street_bets.entry(&action.player_name).or_default() += amount,
+                        Action::PostSmallAndBigBlind(amount) => *street_bets.entry(&action.player_name).or_default() += amount - small_blind,
+                        _ => {}
+                    }
+                }
+            }
+            for action in actions.iter() {
+                let name = action.player_name.as_str();
+                match action.action {
+                    Action::Bet(amount) | Action::BetAndAllIn(amount) | Action::Call(amount) | Action::CallAndAllIn(amount) => *street_bets.entry(name).or_default() += amount,
+                    Action::Raise(_, to) | Action::RaiseAndAllIn(_, to) => { street_bets.insert(name, to); },
+                    Action::UncalledBet(amount) => {
+                        let own_bet = street_bets.get(name).copied().unwrap_or_default();
+                        let matched = street_bets.iter().filter(|(other, _)| **other != name).map(|(_, bet)| *bet).max().unwrap_or_default();
+                        let expected = own_bet - matched;
+                        if amount != expected {
+                            issues.push(ValidationIssue::UncalledBet { player: name.to_string(), amount, expected });
+                        }
+                        *street_bets.entry(name).or_default() -= amount;
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        // Dead blinds or other money we don't track would make a legal bet look too big, so this is only reported
+        if self.game_info.limit == LimitType::Pot {
+            issues.extend(self.check_pot_limit());
+        }
+
+        let pots: Money = self.pots.iter().map(|pot| pot.amount).sum();
+        if pots != self.total_pot {
+            issues.push(ValidationIssue::Pots { pots, total_pot: self.total_pot });
+        }
+
+        let collected: Money = Self::winners(self.actions().all()).iter().map(|(_, amount)| *amount).sum();
+        if collected != self.total_pot - self.rake {
+            issues.push(ValidationIssue::Collected { collected, expected: self.total_pot - self.rake });
+        }
+
+        // The summary is an independent record of the hand: its folds, winnings and shown cards must match the actions
+        for seat in &self.summary {
+            match &seat.outcome {
+                SeatOutcome::Folded { street, .. } => {
+                    let actions = match street {
+                        Street::Flop => &self.flop_actions,
+                        Street::Turn => &self.turn_actions,
+                        Street::River => &self.river_actions,
+                        _ => &self.preflop_actions,
+                    };
+                    if !actions.iter().any(|action| action.player_name == seat.name && matches!(action.action, Action::Fold)) {
+                        issues.push(ValidationIssue::SummaryFold { player: seat.name.clone(), street: street.as_str().to_string() });
+                    }
+                },
+                SeatOutcome::Showed { won: summary_won, .. } | SeatOutcome::Collected(summary_won) => {
+                    let collected = Self::winners(self.actions().all()).into_iter().find(|(name, _)| *name == seat.name).map_or(Money::ZERO, |(_, amount)| amount);
+                    if collected != *summary_won {
+                        issues.push(ValidationIssue::SummaryWinnings { player: seat.name.clone(), summary: *summary_won, collected });
+                    }
+                },
+                _ => {}
+            }
+            let summary_cards = match &seat.outcome {
+                SeatOutcome::Showed { cards, .. } | SeatOutcome::Mucked { cards: Some(cards) } => Some(cards),
+                _ => None,
+            };
+            if let (Some(summary_cards), Some(player)) = (summary_cards, self.players.iter().find(|player| player.name == seat.name))
+                && player.hole_cards.as_ref() != Some(summary_cards) {
+                issues.push(ValidationIssue::SummaryCards { player: seat.name.clone(), cards: join_cards(summary_cards) });
+            }
+        }
 
-        (total_pot, main_pot, side_pot, side_pot2, rake)
+        // Chips a player adds are already part of chips_after_hand in the hand the top-up is reported in
+        if let Some(next_hand) = next_hand {
+            // In a Zoom pool only hero follows from one hand to the next; everyone else was at another table
+            for player in self.players.iter().filter(|player| !self.game_info.is_zoom || player.is_hero) {
+                if let Some(next_player) = next_hand.players.iter().find(|next_player| next_player.name == player.name)
+                    && next_player.chips != player.chips_after_hand {
+                    issues.push(ValidationIssue::Stack {
+                        player: player.name.clone(),
+                        chips_after_hand: player.chips_after_hand,
+                        next_hand_id: next_hand.id.clone(),
+                        next_hand_chips: next_player.chips,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    // Every seated player's net result, in the hand's currency, in big blinds and, given a reporting
+    // conversion, in the reporting currency
+    fn results_json(&self, reporting: Option<&(String, f32)>) -> String {
+        self.players.iter()
+            .map(|player| {
+                let net = player.chips_after_hand - player.chips;
+                format!(
+                    "{{\"name\":\"{}\",\"net\":{},\"net_bb\":{},\"net_reporting\":{}}}",
+                    player.name.replace("\"", "\\\""),
+                    net,
+                    net.cents() as f64 / self.game_info.big_blind.cents() as f64,
+                    reporting.map_or("null".to_string(), |(_, rate)| net.convert(*rate).to_string())
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",")
     }
 
-    fn to_json(&self, user_google_id: String, google_access_token: String) -> String {
+    // `reporting` is the reporting currency and the rate to convert this hand's amounts into it
+    fn to_json(&self, user_google_id: String, google_access_token: String, reporting: Option<(String, f32)>) -> String {
+        let reporting_json = match &reporting {
+            Some((currency, rate)) => format!(
+                "{{\"currency\":\"{}\",\"rate\":{},\"total_pot\":{},\"rake\":{}}}",
+                currency.replace("\"", "\\\""),
+                rate,
+                self.total_pot.convert(*rate),
+                self.rake.convert(*rate)
+            ),
+            None => "null".to_string(),
+        };
         format!(
-            "{{\"id\":\"{}\",\"date\":\"{}\",\"time\":\"{}\",\"table_name\":\"{}\",\"small_blind\":{},\"max_players\":{},\"dealer_seat\":{},\"players\":[{}],\"pre_actions\":[{}],\"preflop_actions\":[{}],\"flop_actions\":[{}],\"turn_actions\":[{}],\"river_actions\":[{}],\"show_down_actions\":[{}],\"hero_cards\":[{}],\"hero_name\":\"{}\",\"community_cards\":[{}],\"total_pot\":{},\"main_pot\":{},\"side_pot\":{},\"side_pot2\":{},\"rake\":{},\"user_google_id\":\"{}\",\"google_access_token\":\"{}\"}}",
+            "{{\"id\":\"{}\",\"date\":\"{}\",\"time\":\"{}\",\"time_zone\":\"{}\",\"timestamp\":\"{}\",\"language\":\"{}\",\"table_name\":\"{}\",\"logical_table\":\"{}\",\"small_blind\":{},\"big_blind\":{},\"ante\":{},\"currency\":\"{}\",\"reporting\":{},\"game_info\":{},\"kind\":\"{}\",\"tournament\":{},\"max_players\":{},\"dealer_seat\":{},\"players\":[{}],\"pre_actions\":[{}],\"preflop_actions\":[{}],\"flop_actions\":[{}],\"turn_actions\":[{}],\"river_actions\":[{}],\"show_down_actions\":[{}],\"timeline\":[{}],\"hero_cards\":{},\"hero_name\":{},\"is_observed\":{},\"community_cards\":[{}],\"boards\":[{}],\"pots\":[{}],\"total_pot\":{},\"main_pot\":{},\"side_pot\":{},\"side_pot2\":{},\"rake\":{},\"summary\":[{}],\"results\":[{}],\"unknown_lines\":{},\"user_google_id\":\"{}\",\"google_access_token\":\"{}\"}}",
             self.id.replace("\"", "\\\""),
             self.date.replace("\"", "\\\""),
             self.time.replace("\"", "\\\""),
+            self.time_zone,
+            self.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            self.language.as_str(),
             self.table_name.replace("\"", "\\\""),
-            self.small_blind,
+            self.logical_table().replace("\"", "\\\""),
+            self.game_info.small_blind,
+            self.game_info.big_blind,
+            self.ante,
+            self.game_info.currency,
+            reporting_json,
+            self.game_info.to_json(),
+            self.kind.as_str(),
+            self.kind.to_json(),
             self.max_players,
             self.dealer_seat,
             self.players.iter().map(|p| p.to_json()).collect::<Vec<_>>().join(","),
@@ -756,14 +1562,21 @@ impl Hand {
             self.turn_actions.iter().map(|a| a.to_json()).collect::<Vec<_>>().join(","),
             self.river_actions.iter().map(|a| a.to_json()).collect::<Vec<_>>().join(","),
             self.show_down_actions.iter().map(|a| a.to_json()).collect::<Vec<_>>().join(","),
-            self.hero_cards.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(","),
-            self.hero_name.replace("\"", "\\\""),
+            build_timeline(self).iter().map(|e| e.to_json()).collect::<Vec<_>>().join(","),
+            self.hero_cards.as_ref().map_or("null".to_string(), |cards| format!("[{}]", cards.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(","))),
+            self.hero_name.as_ref().map_or("null".to_string(), |hero_name| format!("\"{}\"", hero_name.replace("\"", "\\\""))),
+            self.is_observed(),
             self.community_cards.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(","),
+            self.boards.iter().map(|b| b.to_json()).collect::<Vec<_>>().join(","),
+            self.pots.iter().map(|p| p.to_json()).collect::<Vec<_>>().join(","),
             self.total_pot,
             self.main_pot,
             self.side_pot,
             self.side_pot2,
             self.rake,
+            self.summary.iter().map(|s| s.to_json()).collect::<Vec<_>>().join(","),
+            self.results_json(reporting.as_ref()),
+            self.unknown_lines,
             user_google_id,
             google_access_token
         )
@@ -772,9 +1585,15 @@ impl Hand {
     fn print(&self) {
         println!("ID: {}", self.id);
         println!("Date: {}", self.date);
-        println!("Time: {}", self.time);
+        println!("Time: {} {} ({} UTC)", self.time, self.time_zone, self.timestamp.format("%Y/%m/%d %H:%M:%S"));
         println!("Table Name: {}", self.table_name);
-        println!("Small Blind: {}", self.small_blind);
+        if let HandKind::Tournament(info) = &self.kind {
+            println!("Tournament: #{} level {}", info.id, info.level);
+        }
+        println!("Game: {} {} ({})", self.game_info.variant.as_str(), self.game_info.limit.as_str(), self.game_info.currency);
+        println!("Small Blind: {}", self.game_info.small_blind);
+        println!("Big Blind: {}", self.game_info.big_blind);
+        println!("Ante: {}", self.ante);
         println!("Max Players: {}", self.max_players);
         println!("Dealer Position: {}", self.dealer_seat);
         
@@ -789,7 +1608,9 @@ impl Hand {
         }
     
         println!("\nPlayer Cards:");
-        println!("{}: {} {}", self.hero_name, self.hero_cards[0], self.hero_cards[1]);
+        if let (Some(hero_name), Some(hero_cards)) = (&self.hero_name, &self.hero_cards) {
+            println!("{}: {}", hero_name, join_cards(hero_cards));
+        }
     
         println!("\nPreflop Actions:");
         for action in &self.preflop_actions {
@@ -833,6 +1654,15 @@ impl Hand {
         for action in &self.show_down_actions {
             action.print();
         }
+
+        if self.boards.len() > 1 {
+            for (index, board) in self.boards.iter().enumerate() {
+                println!("\nBoard {}: {}", index + 1, join_cards(&board.cards));
+                for (name, amount) in &board.winners {
+                    println!("  {} won ${:.2}", name, amount);
+                }
+            }
+        }
     
         println!("\nTotal pot: ${:.2} | Main pot: ${:.2} | Side pot: ${:.2} | Side pot 2: ${:.2} | Rake: ${:.2}", self.total_pot, self.main_pot, self.side_pot, self.side_pot2, self.rake);
     
@@ -846,32 +1676,47 @@ fn get_hand_files_from_folder(path_to_pokerstars_handhistory: &str) -> Vec<Resul
     files
 }
 
-fn get_hands_from_file(file_path: &str) -> Vec<Hand> {
+// A file that can't be read or decoded is one failure rather than the end of the sync
+fn read_hand_file_or_error(file_path: &str) -> Result<String, HandParseError> {
+    read_hand_file(file_path).map_err(|e| HandParseError::new(HandParseErrorKind::UnreadableFile(e.to_string()), file_path))
+}
+
+fn get_hands_from_file(file_path: &str, mode: ParseMode) -> Vec<Result<Hand, HandParseError>> {
     let mut all_hands = Vec::new();
-    let contents = fs::read_to_string(file_path).unwrap();
-    //let hands: Vec<&str> = vec![contents.split("\r\n\r\n\r\n").collect::<Vec<&str>>().first().unwrap()]; // only parse the first hand
-    let hands: Vec<&str> = contents.split("\r\n\r\n\r\n").collect();
+    let contents = match read_hand_file_or_error(file_path) {
+        Ok(contents) => contents,
+        Err(e) => return vec![Err(e)],
+    };
+    //let hands: Vec<&str> = vec![split_hands(&contents).first().unwrap()]; // only parse the first hand
+    let hands: Vec<&str> = split_hands(&contents);
     for hand_str in hands {
-        let is_tournament = (hand_str.lines().find(|line| line.contains("Tournament #"))).is_some();
-        if !hand_str.trim().is_empty() && !is_tournament {
-            let hand = Hand::from_str(hand_str);
-            all_hands.push(hand);
-        }
+        let hand = Hand::from_str(hand_str, mode);
+        all_hands.push(hand);
     }
     all_hands
 }
 
-fn get_last_hand_from_file(file_path: &str) -> Hand {
-    let contents = fs::read_to_string(file_path).unwrap();
-    let last_hand_str = "PokerStars Hand".to_string()+contents.split("PokerStars Hand").last().unwrap();
-    let hand = Hand::from_str(last_hand_str.as_str());
-    hand
+fn get_last_hand_from_file(file_path: &str, mode: ParseMode) -> Result<Hand, HandParseError> {
+    let contents = read_hand_file_or_error(file_path)?;
+    let last_hand_str = split_hands(&contents).last().copied().unwrap_or("");
+    Hand::from_str(last_hand_str, mode)
 }
 
 fn get_hand_by_id(all_hands: &Vec<Hand>, id: &str) -> Option<Hand> {
     all_hands.iter().find(|hand| hand.id == id).cloned()
 }
 
+// The following hand at the same table, or in the same pool for Zoom
+fn get_next_hand_at_table<'a>(all_hands: &'a [Hand], hand: &Hand) -> Option<&'a Hand> {
+    let id = hand.id.parse::<u64>().ok()?;
+    all_hands.iter()
+        .filter(|other| other.logical_table() == hand.logical_table())
+        .filter_map(|other| Some((other.id.parse::<u64>().ok()?, other)))
+        .filter(|(other_id, _)| *other_id > id)
+        .min_by_key(|(other_id, _)| *other_id)
+        .map(|(_, other)| other)
+}
+
 fn scan_for_todays_most_recent_hand(path_to_pokerstars_handhistory: &str) -> Option<Hand> {
     let files = get_hand_files_from_folder(path_to_pokerstars_handhistory);
     let today = chrono::Local::now().date_naive();
@@ -895,17 +1740,23 @@ fn scan_for_todays_most_recent_hand(path_to_pokerstars_handhistory: &str) -> Opt
     }).collect();
 
 
-    let last_hand = today_files.iter().map(|file| 
-        get_last_hand_from_file(file.path().to_str().unwrap()))
-        .max_by_key(|hand| chrono::NaiveDateTime::parse_from_str(&format!("{} {}", hand.date, hand.time), "%Y/%m/%d %H:%M:%S").unwrap());
+    let last_hand = today_files.iter().filter_map(|file| 
+        match get_last_hand_from_file(file.path().to_str().unwrap(), ParseMode::Lenient) {
+            Ok(hand) => Some(hand),
+            Err(e) => {
+                eprintln!("Failed to parse last hand of {}: {}", file.path().display(), e);
+                None
+            }
+        })
+        .max_by_key(|hand| hand.timestamp);
     last_hand
 }
 
-async fn send_hand_to_server(hand: Hand, user_google_id: String, google_access_token: String) {
+async fn send_hand_to_server(hand: Hand, user_google_id: String, google_access_token: String, reporting: Option<(String, f32)>) {
     let client = reqwest::Client::new();
     println!("Sending hand to server: {}/api/hand", BACKEND_URL);
     let url = format!("{}/api/hand", BACKEND_URL);
-    let json = hand.to_json(user_google_id, google_access_token);
+    let json = hand.to_json(user_google_id, google_access_token, reporting);
     let res = client
         .post(url)
         .header("Content-Type", "application/json")
@@ -1125,6 +1976,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
     println!("Args: {:?}", args);
 
+    if args.len() > 1 && args[1] == "--bench-parse" {
+        // Benchmark mode: parse a synthetic history file and report hands/second
+        let hand_count = args.get(2).and_then(|count| count.parse::<usize>().ok()).unwrap_or(100_000);
+        bench::run_parse_benchmark(hand_count)?;
+        return Ok(());
+    }
+
     // Create a Tokio runtime for async operations
     let rt = tokio::runtime::Runtime::new()?;
 
@@ -1201,11 +2059,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
                         };
                         
+                        let config = config_manager.load_config().unwrap_or_default();
                         let files = get_hand_files_from_folder(&path_to_handhistory);
-                        let hands = files.iter().map(|file| get_hands_from_file(file.as_ref().unwrap().path().to_str().unwrap())).flatten().collect::<Vec<Hand>>();
-                        for hand in &hands[0..5] {
-                            println!("{}", hand.to_json( user_info.id.clone(), google_access_token.clone().unwrap()));
-                            rt.block_on(send_hand_to_server(hand.clone(),  user_info.id.clone(), google_access_token.clone().unwrap()));
+                        let (hands, parse_errors): (Vec<_>, Vec<_>) = files.iter().map(|file| get_hands_from_file(file.as_ref().unwrap().path().to_str().unwrap(), ParseMode::Lenient)).flatten().partition(|result| result.is_ok());
+                        let mut hands = hands.into_iter().map(Result::unwrap).collect::<Vec<Hand>>();
+                        for hand in &mut hands {
+                            hand.identify_hero(&config.screen_names);
+                        }
+                        let parse_errors = parse_errors.into_iter().map(Result::unwrap_err).collect::<Vec<HandParseError>>();
+                        println!("Main: Parsed {} hands, skipped {} hands with errors", hands.len(), parse_errors.len());
+                        for parse_error in &parse_errors {
+                            eprintln!("Skipped hand: {}", parse_error);
+                        }
+                        for session in group_sessions(&hands) {
+                            println!("Main: Session at {} from {} to {}: {} hands", session.logical_table, session.start, session.end, session.hand_ids.len());
+                        }
+                        for hand in hands.iter().take(5) {
+                            if hand.is_observed() && config.skip_observed_hands {
+                                eprintln!("Skipped hand #{}: observed, none of the configured screen names is seated", hand.id);
+                                continue;
+                            }
+                            let issues = hand.validate(get_next_hand_at_table(&hands, hand));
+                            for issue in &issues {
+                                eprintln!("Hand #{} failed validation: {}", hand.id, issue);
+                            }
+                            if !issues.is_empty() && config.block_invalid_uploads {
+                                continue;
+                            }
+                            let reporting = config.reporting_conversion(&hand.game_info.currency);
+                            println!("{}", hand.to_json( user_info.id.clone(), google_access_token.clone().unwrap(), reporting.clone()));
+                            rt.block_on(send_hand_to_server(hand.clone(),  user_info.id.clone(), google_access_token.clone().unwrap(), reporting));
                         }
                         
                     }
@@ -1296,3 +2179,472 @@ async fn load_icon_from_url(url: &str, width: u32, height: u32) -> tray_icon::me
     let rgba = resized_image.into_rgba8().into_raw();
     tray_icon::menu::Icon::from_rgba(rgba, width, height).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The seat list of the adversarial hand in bench::BENCH_HANDS
+    fn adversarial_seats() -> Vec<String> {
+        ["Bob: x", "Al collected", "Ünïcødé 🂡", "[brackets] (paren)", "trailing ", "Bob"].iter().map(|name| name.to_string()).collect()
+    }
+
+    fn parse_action(line: &str) -> PlayerAction {
+        PlayerAction::from_str(line, &adversarial_seats()).unwrap()
+    }
+
+    #[test]
+    fn attributes_actions_to_adversarial_screen_names() {
+        let action = parse_action("Bob: x: folds");
+        assert_eq!(action.player_name, "Bob: x");
+        assert!(matches!(action.action, Action::Fold));
+
+        let action = parse_action("Al collected: posts small blind $0.02");
+        assert_eq!(action.player_name, "Al collected");
+        assert!(matches!(action.action, Action::PostSmallBlind(amount) if amount == Money::from_cents(2)));
+
+        let action = parse_action("Ünïcødé 🂡: posts big blind $0.05");
+        assert_eq!(action.player_name, "Ünïcødé 🂡");
+        assert!(matches!(action.action, Action::PostBigBlind(amount) if amount == Money::from_cents(5)));
+
+        let action = parse_action("[brackets] (paren): raises $0.10 to $0.15");
+        assert_eq!(action.player_name, "[brackets] (paren)");
+        assert!(matches!(action.action, Action::Raise(by, to) if by == Money::from_cents(10) && to == Money::from_cents(15)));
+
+        let action = parse_action("trailing : raises $4.55 to $5 and is all-in");
+        assert_eq!(action.player_name, "trailing ");
+        assert!(matches!(action.action, Action::RaiseAndAllIn(by, to) if by == Money::from_cents(455) && to == Money::from_cents(500)));
+
+        let action = parse_action("Bob: raises $0.30 to $0.45");
+        assert_eq!(action.player_name, "Bob");
+        assert!(matches!(action.action, Action::Raise(by, to) if by == Money::from_cents(30) && to == Money::from_cents(45)));
+    }
+
+    #[test]
+    fn attributes_collections_and_returns_to_adversarial_screen_names() {
+        let action = parse_action("trailing  collected $1.12 from pot");
+        assert_eq!(action.player_name, "trailing ");
+        assert!(matches!(action.action, Action::Collected(amount) if amount == Money::from_cents(112)));
+
+        let action = parse_action("Uncalled bet ($4.55) returned to trailing ");
+        assert_eq!(action.player_name, "trailing ");
+        assert!(matches!(action.action, Action::UncalledBet(amount) if amount == Money::from_cents(455)));
+
+        let action = parse_action("Bob is connected");
+        assert_eq!(action.player_name, "Bob");
+        assert!(matches!(action.action, Action::Connected));
+    }
+
+    #[test]
+    fn parses_table_events_with_their_full_wording() {
+        let action = parse_action("Bob joins the table at seat #4");
+        assert_eq!(action.player_name, "Bob");
+        assert!(matches!(action.action, Action::Join));
+
+        let action = parse_action("Bob has timed out while disconnected");
+        assert_eq!(action.player_name, "Bob");
+        assert!(matches!(action.action, Action::TimedOut));
+
+        let action = parse_action("Bob has timed out while being disconnected");
+        assert!(matches!(action.action, Action::TimedOut));
+
+        let action = parse_action("Bob has timed out");
+        assert!(matches!(action.action, Action::TimedOut));
+    }
+
+    const ANTE_AND_STRADDLE_HAND: &str = "PokerStars Hand #250123456793:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:27:00 CET [2024/11/03 8:27:00 ET]
+Table 'Aarhus II' 6-max Seat #1 is the button
+Seat 1: A ($5 in chips)
+Seat 2: B ($5 in chips)
+Seat 3: C ($5 in chips)
+Seat 4: D ($5 in chips)
+A: posts the ante $0.01
+B: posts the ante $0.01
+C: posts the ante $0.01
+D: posts the ante $0.01
+B: posts small blind $0.02
+C: posts big blind $0.05
+D: posts straddle $0.10
+*** HOLE CARDS ***
+Dealt to A [Ah Ad]
+A: raises $0.20 to $0.30
+B: folds
+C: folds
+D: calls $0.20
+*** FLOP *** [2c 3d 4h]
+D: checks
+A: bets $0.40
+D: folds
+Uncalled bet ($0.40) returned to A
+A collected $0.71 from pot
+*** SUMMARY ***
+Total pot $0.71 | Rake $0
+Board [2c 3d 4h]";
+
+    #[test]
+    fn keeps_the_seat_position_of_a_straddler() {
+        let hand = Hand::from_str(ANTE_AND_STRADDLE_HAND, ParseMode::Strict).unwrap();
+        let positions: Vec<&str> = hand.players.iter().map(|player| player.position.as_ref().unwrap().as_str()).collect();
+        assert_eq!(positions, ["BTN", "SB", "BB", "UTG"]);
+        assert!(hand.pre_actions.iter().any(|action| action.player_name == "D" && matches!(action.action, Action::PostStraddle(amount) if amount == Money::from_cents(10))));
+    }
+
+    const KNOCKOUT_TOURNAMENT_HAND: &str = "PokerStars Hand #250223456789: Tournament #3456789012, $3.19+$3.31+$0.50 USD Hold'em No Limit - Level IV (25/50) - 2024/11/03 14:22:10 CET [2024/11/03 8:22:10 ET]
+Table '3456789012 1' 6-max Seat #1 is the button
+Seat 1: Alice (1,500 in chips, $3.31 bounty)
+Seat 2: Hero (2,000 in chips, $3.31 bounty)
+Seat 3: Bob (400 in chips, $3.31 bounty)
+Seat 4: Carl (1,200 in chips, $3.31 bounty) out of hand (moved from another table into small blind)
+Alice: posts the ante 5
+Hero: posts the ante 5
+Bob: posts the ante 5
+Hero: posts small blind 25
+Bob: posts big blind 50
+*** HOLE CARDS ***
+Dealt to Hero [Ah Ad]
+Alice: folds
+Hero: raises 100 to 150
+Bob: calls 100
+*** FLOP *** [2c 5d 9h]
+Hero: bets 245 and is all-in
+Bob: calls 245 and is all-in
+*** TURN *** [2c 5d 9h] [Js]
+*** RIVER *** [2c 5d 9h Js] [3c]
+*** SHOW DOWN ***
+Hero: shows [Ah Ad] (a pair of Aces)
+Bob: shows [Kc Qc] (high card King)
+Hero collected 805 from pot
+Hero wins $1.65 for eliminating Bob and their own bounty increases by $1.66 to $4.97
+Bob finished the tournament in 4th place
+*** SUMMARY ***
+Total pot 805 | Rake 0
+Board [2c 5d 9h Js 3c]
+Seat 1: Alice (button) folded before Flop (didn't bet)
+Seat 2: Hero (small blind) showed [Ah Ad] and won (805) with a pair of Aces
+Seat 3: Bob (big blind) showed [Kc Qc] and lost with high card King";
+
+    #[test]
+    fn parses_a_knockout_tournament_hand() {
+        let hand = Hand::from_str(KNOCKOUT_TOURNAMENT_HAND, ParseMode::Strict).unwrap();
+        let HandKind::Tournament(info) = &hand.kind else { panic!("not parsed as a tournament") };
+        assert_eq!(info.id, "3456789012");
+        assert_eq!((info.buy_in, info.bounty, info.fee), (Money::from_cents(319), Money::from_cents(331), Money::from_cents(50)));
+        assert_eq!(info.currency, "USD");
+        assert_eq!(info.level, 4);
+
+        // Tournament chips are bare numbers, not currency
+        assert_eq!((hand.game_info.small_blind, hand.game_info.big_blind, hand.ante), (Money::parse("25").unwrap(), Money::parse("50").unwrap(), Money::parse("5").unwrap()));
+        let player = |name: &str| hand.players.iter().find(|player| player.name == name).unwrap();
+        assert!(player("Carl").is_sitting_out);
+        assert_eq!(player("Hero").chips_after_hand, Money::parse("2405").unwrap());
+        assert_eq!(player("Bob").chips_after_hand, Money::ZERO);
+
+        assert!(hand.show_down_actions.iter().any(|action| action.player_name == "Hero" && matches!(&action.action, Action::WonBounty(amount, eliminated) if *amount == Money::from_cents(165) && eliminated == "Bob")));
+        assert!(hand.show_down_actions.iter().any(|action| action.player_name == "Bob" && matches!(action.action, Action::FinishedTournament(Some(4), prize) if prize == Money::ZERO)));
+        assert!(hand.validate(None).is_empty());
+
+        let freeroll = "PokerStars Hand #250223456790: Tournament #3456789013, Freeroll  Hold'em No Limit - Match Round I, Level XIV (10/20) - 2024/11/03 14:25:10 CET [2024/11/03 8:25:10 ET]";
+        let HandKind::Tournament(info) = HandKind::from_header(freeroll).unwrap() else { panic!("not parsed as a tournament") };
+        assert_eq!((info.buy_in, info.fee, info.level), (Money::ZERO, Money::ZERO, 14));
+    }
+
+    const FIVE_CARD_OMAHA_HAND: &str = "PokerStars Hand #250323456790:  5 Card Omaha Pot Limit ($0.02/$0.05 USD) - 2024/11/03 14:23:10 CET [2024/11/03 8:23:10 ET]
+Table 'Aarhus III' 6-max Seat #2 is the button
+Seat 1: Alice ($5 in chips)
+Seat 2: Hero ($5 in chips)
+Seat 3: Bob ($5 in chips)
+Bob: posts small blind $0.02
+Alice: posts big blind $0.05
+*** HOLE CARDS ***
+Dealt to Hero [Ah Ad Kc Qs 7h]
+Hero: raises $0.12 to $0.17
+Bob: folds
+Alice: calls $0.12
+*** FLOP *** [2c 5d 9h]
+Alice: checks
+Hero: bets $0.36
+Alice: calls $0.36
+*** TURN *** [2c 5d 9h] [Js]
+Alice: checks
+Hero: checks
+*** RIVER *** [2c 5d 9h Js] [3c]
+Alice: checks
+Hero: checks
+*** SHOW DOWN ***
+Hero: shows [Ah Ad Kc Qs 7h] (a pair of Aces)
+Alice: shows [Kd Kh 8c 8d 4s] (a pair of Kings)
+Hero collected $1.08 from pot
+*** SUMMARY ***
+Total pot $1.08 | Rake $0
+Board [2c 5d 9h Js 3c]";
+
+    #[test]
+    fn reports_bets_over_the_pot_limit_without_dropping_the_hand() {
+        let hand = Hand::from_str(FIVE_CARD_OMAHA_HAND, ParseMode::Strict).unwrap();
+        assert_eq!(hand.game_info.limit, LimitType::Pot);
+        assert_eq!(hand.hero_cards.as_ref().unwrap().len(), 5);
+        assert!(hand.validate(None).is_empty());
+
+        // The flop pot is $0.36, so a $0.40 bet is over the limit
+        let overbet = FIVE_CARD_OMAHA_HAND
+            .replace("Hero: bets $0.36\nAlice: calls $0.36", "Hero: bets $0.40\nAlice: calls $0.40")
+            .replace("$1.08", "$1.16");
+        let hand = Hand::from_str(&overbet, ParseMode::Strict).unwrap();
+        assert_eq!(hand.validate(None), [ValidationIssue::PotLimit {
+            player: "Hero".to_string(),
+            street: "Flop".to_string(),
+            to: Money::from_cents(40),
+            pot_limit: Money::from_cents(36),
+        }]);
+    }
+
+    const RUN_TWICE_HAND: &str = "PokerStars Hand #250423456789:  Hold'em No Limit ($0.05/$0.10 USD) - 2024/11/03 14:22:10 CET [2024/11/03 8:22:10 ET]
+Table 'Aarhus IV' 6-max Seat #1 is the button
+Seat 1: Alice ($10 in chips)
+Seat 2: Hero ($10 in chips)
+Seat 3: Bob ($12 in chips)
+Hero: posts small blind $0.05
+Bob: posts big blind $0.10
+*** HOLE CARDS ***
+Dealt to Hero [Ah Kh]
+Alice: folds
+Hero: raises $9.90 to $10 and is all-in
+Bob: calls $9.90
+*** FIRST FLOP *** [2c 5d 9h]
+*** FIRST TURN *** [2c 5d 9h] [Js]
+*** FIRST RIVER *** [2c 5d 9h Js] [3c]
+*** SECOND FLOP *** [Kd 7s 4c]
+*** SECOND TURN *** [Kd 7s 4c] [Qh]
+*** SECOND RIVER *** [Kd 7s 4c Qh] [8d]
+*** FIRST SHOW DOWN ***
+Hero: shows [Ah Kh] (high card Ace)
+Bob: shows [Qs Qd] (a pair of Queens)
+Bob collected $9.75 from pot
+*** SECOND SHOW DOWN ***
+Hero collected $9.75 from pot
+*** SUMMARY ***
+Total pot $20 | Rake $0.50
+Hand was run twice
+FIRST Board [2c 5d 9h Js 3c]
+SECOND Board [Kd 7s 4c Qh 8d]
+Seat 1: Alice (button) folded before Flop (didn't bet)
+Seat 2: Hero (small blind) showed [Ah Kh] and lost with high card Ace, and won ($9.75) with a pair of Kings
+Seat 3: Bob (big blind) showed [Qs Qd] and won ($9.75) with a pair of Queens, and lost with a pair of Queens";
+
+    #[test]
+    fn splits_a_hand_run_twice_into_two_boards() {
+        let hand = Hand::from_str(RUN_TWICE_HAND, ParseMode::Strict).unwrap();
+        assert_eq!(hand.boards.len(), 2);
+        assert_eq!(hand.boards[0].cards, parse_cards("2c 5d 9h Js 3c").unwrap());
+        assert_eq!(hand.boards[1].cards, parse_cards("Kd 7s 4c Qh 8d").unwrap());
+        assert_eq!(hand.boards[0].winners, [("Bob".to_string(), Money::from_cents(975))]);
+        assert_eq!(hand.boards[1].winners, [("Hero".to_string(), Money::from_cents(975))]);
+        assert_eq!(hand.community_cards, hand.boards[0].cards);
+
+        let player = |name: &str| hand.players.iter().find(|player| player.name == name).unwrap();
+        assert_eq!(player("Hero").chips_after_hand, Money::from_cents(975));
+        assert_eq!(player("Bob").chips_after_hand, Money::from_cents(1175));
+        assert!(hand.validate(None).is_empty());
+    }
+
+    const SIDE_POTS_HAND: &str = "PokerStars Hand #250523456789:  Hold'em No Limit ($0.05/$0.10 USD) - 2024/11/03 14:22:10 CET [2024/11/03 8:22:10 ET]
+Table 'Aarhus V' 6-max Seat #1 is the button
+Seat 1: Alice ($2 in chips)
+Seat 2: Hero ($10 in chips)
+Seat 3: Bob ($5 in chips)
+Seat 4: Carl ($10 in chips)
+Seat 5: Dave ($3 in chips)
+Hero: posts small blind $0.05
+Bob: posts big blind $0.10
+*** HOLE CARDS ***
+Dealt to Hero [Ah Kh]
+Carl: raises $0.20 to $0.30
+Dave: folds
+Alice: raises $1.70 to $2 and is all-in
+Hero: raises $8 to $10 and is all-in
+Bob: calls $4.90 and is all-in
+Carl: calls $9.70 and is all-in
+*** FLOP *** [2c 5d 9h]
+*** TURN *** [2c 5d 9h] [Js]
+*** RIVER *** [2c 5d 9h Js] [3c]
+*** SHOW DOWN ***
+Hero: shows [Ah Kh] (high card Ace)
+Bob: shows [Qs Qd] (a pair of Queens)
+Carl: shows [Jc Jd] (three of a kind, Jacks)
+Alice: shows [7c 7d] (a pair of Sevens)
+Carl collected $10 from side pot-2
+Carl collected $9 from side pot-1
+Carl collected $8 from main pot
+*** SUMMARY ***
+Total pot $27 Main pot $8. Side pot-1 $9. Side pot-2 $10. | Rake $0
+Board [2c 5d 9h Js 3c]";
+
+    #[test]
+    fn builds_a_main_pot_and_numbered_side_pots() {
+        let hand = Hand::from_str(SIDE_POTS_HAND, ParseMode::Strict).unwrap();
+        assert_eq!((hand.main_pot, hand.side_pot, hand.side_pot2), (Money::from_cents(800), Money::from_cents(900), Money::from_cents(1000)));
+
+        let pots: Vec<(Money, Vec<&str>)> = hand.pots.iter()
+            .map(|pot| (pot.amount, pot.eligible_players.iter().map(|name| name.as_str()).collect()))
+            .collect();
+        assert_eq!(pots, [
+            (Money::from_cents(800), vec!["Alice", "Hero", "Bob", "Carl"]),
+            (Money::from_cents(900), vec!["Hero", "Bob", "Carl"]),
+            (Money::from_cents(1000), vec!["Hero", "Carl"]),
+        ]);
+        for pot in &hand.pots {
+            assert_eq!(pot.winners, [("Carl".to_string(), pot.amount)]);
+        }
+
+        let player = |name: &str| hand.players.iter().find(|player| player.name == name).unwrap();
+        assert_eq!(player("Carl").chips_after_hand, Money::from_cents(2700));
+        assert_eq!(player("Alice").chips_after_hand, Money::ZERO);
+        assert!(hand.validate(None).is_empty());
+    }
+
+    #[test]
+    fn counts_each_players_shown_cards_once() {
+        // Bob is hero, dealt [Ah Kd], on a [2c 5d 9h] flop
+        let hero = (parse_cards("Ah Kd").unwrap(), "Bob".to_string());
+        let boards = [Board { cards: parse_cards("2c 5d 9h").unwrap(), winners: Vec::new() }];
+        let check = |shows: &[(&str, &str)]| {
+            let actions = shows.iter()
+                .map(|(player, cards)| parse_action(&format!("{}: shows [{}] (high card)", player, cards)))
+                .collect::<Vec<PlayerAction>>();
+            Hand::check_duplicate_cards(Some(&hero), &boards, actions.iter())
+        };
+
+        // A partial show, a reordered show and a partial show followed by the full hand reveal the same cards
+        assert!(check(&[("Bob", "Ah")]).is_ok());
+        assert!(check(&[("Bob", "Kd Ah")]).is_ok());
+        assert!(check(&[("Bob", "Ah"), ("Bob", "Ah Kd")]).is_ok());
+        assert!(check(&[("trailing ", "Qs"), ("trailing ", "Jc Qs")]).is_ok());
+
+        // The same card in two players' hands, or in a hand and on the board, was still dealt twice
+        assert!(check(&[("trailing ", "Kd Qs")]).is_err());
+        assert!(check(&[("trailing ", "Qs"), ("trailing ", "Qs 9h")]).is_err());
+    }
+
+    const TOP_UP_HANDS: [&str; 2] = [
+        "PokerStars Hand #250123456796:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:31:00 CET [2024/11/03 8:31:00 ET]
+Table 'Aarhus II' 6-max Seat #1 is the button
+Seat 1: Hero ($5 in chips)
+Seat 2: Bob ($5 in chips)
+Seat 3: Carl ($5 in chips)
+Bob: posts small blind $0.02
+Carl: posts big blind $0.05
+Carl adds $5
+*** HOLE CARDS ***
+Dealt to Hero [7c 2d]
+Hero: folds
+Bob: folds
+Uncalled bet ($0.03) returned to Carl
+Carl collected $0.04 from pot
+*** SUMMARY ***
+Total pot $0.04 | Rake $0",
+        "PokerStars Hand #250123456797:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:32:00 CET [2024/11/03 8:32:00 ET]
+Table 'Aarhus II' 6-max Seat #2 is the button
+Seat 1: Hero ($5 in chips)
+Seat 2: Bob ($4.98 in chips)
+Seat 3: Carl ($10.02 in chips)
+Carl: posts small blind $0.02
+Hero: posts big blind $0.05
+*** HOLE CARDS ***
+Dealt to Hero [Ah Kd]
+Bob: folds
+Carl: folds
+Uncalled bet ($0.03) returned to Hero
+Hero collected $0.04 from pot
+*** SUMMARY ***
+Total pot $0.04 | Rake $0",
+    ];
+
+    #[test]
+    fn counts_added_chips_in_the_hand_they_are_added() {
+        let hand = Hand::from_str(TOP_UP_HANDS[0], ParseMode::Strict).unwrap();
+        let next_hand = Hand::from_str(TOP_UP_HANDS[1], ParseMode::Strict).unwrap();
+        let carl = hand.players.iter().find(|player| player.name == "Carl").unwrap();
+        assert_eq!(carl.chips_after_hand, Money::from_cents(1002));
+        assert!(hand.validate(Some(&next_hand)).is_empty());
+
+        // Without the top-up the next hand's stack no longer follows
+        let hand = Hand::from_str(&TOP_UP_HANDS[0].replace("Carl adds $5\n", ""), ParseMode::Strict).unwrap();
+        assert_eq!(hand.validate(Some(&next_hand)), [ValidationIssue::Stack {
+            player: "Carl".to_string(),
+            chips_after_hand: Money::from_cents(502),
+            next_hand_id: "250123456797".to_string(),
+            next_hand_chips: Money::from_cents(1002),
+        }]);
+    }
+
+    #[test]
+    fn detects_hands_only_observed() {
+        let dealt = Hand::from_str(TOP_UP_HANDS[0], ParseMode::Strict).unwrap();
+        assert!(!dealt.is_observed());
+
+        // Without a "Dealt to" line the hand was watched, unless one of the user's screen names is seated
+        let watched = Hand::from_str(&TOP_UP_HANDS[0].replace("Dealt to Hero [7c 2d]\n", ""), ParseMode::Strict).unwrap();
+        assert!(watched.is_observed());
+        assert!(watched.to_json(String::new(), String::new(), None).contains("\"is_observed\":true"));
+
+        let mut other_table = watched.clone();
+        other_table.identify_hero(&["Someone".to_string()]);
+        assert!(other_table.is_observed());
+
+        let mut seated_user = watched.clone();
+        seated_user.identify_hero(&["Someone".to_string(), "Hero".to_string()]);
+        assert!(!seated_user.is_observed());
+        assert_eq!(seated_user.hero_name.as_deref(), Some("Hero"));
+        assert!(seated_user.players.iter().any(|player| player.name == "Hero" && player.is_hero));
+    }
+
+    #[test]
+    fn assigns_positions_from_the_blinds_posted() {
+        // Seats at the table, the seat sitting out, the button seat and the expected positions by seat
+        type Case = (Vec<u32>, Option<u32>, u32, Vec<&'static str>);
+        let cases: Vec<Case> = vec![
+            (vec![1, 2], None, 1, vec!["SB", "BB"]),
+            (vec![1, 2, 3], None, 3, vec!["SB", "BB", "BTN"]),
+            (vec![1, 2, 3, 4], None, 2, vec!["UTG", "BTN", "SB", "BB"]),
+            (vec![1, 2, 3, 4, 5], None, 5, vec!["SB", "BB", "UTG", "CO", "BTN"]),
+            (vec![1, 2, 3, 4, 5, 6], None, 3, vec!["HJ", "CO", "BTN", "SB", "BB", "UTG"]),
+            (vec![1, 2, 3, 4, 5, 6, 7], Some(2), 1, vec!["BTN", "-", "SB", "BB", "UTG", "HJ", "CO"]),
+            (vec![1, 2, 3, 4, 5, 6, 7, 8], None, 8, vec!["SB", "BB", "UTG", "UTG1", "LJ", "HJ", "CO", "BTN"]),
+            (vec![1, 2, 3, 4, 5, 6, 7, 8, 9], None, 9, vec!["SB", "BB", "UTG", "UTG1", "UTG2", "LJ", "HJ", "CO", "BTN"]),
+            (vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10], None, 10, vec!["SB", "BB", "UTG", "UTG1", "UTG2", "UTG3", "LJ", "HJ", "CO", "BTN"]),
+            // Heads-up once a player sits out: the button posts the small blind
+            (vec![2, 5, 9], Some(5), 9, vec!["BB", "-", "SB"]),
+            (vec![1, 3, 4, 6, 8, 9], Some(4), 8, vec!["BB", "UTG", "-", "CO", "BTN", "SB"]),
+        ];
+        for (seats, sitting_out, dealer_seat, expected) in cases {
+            let mut players = seats.iter()
+                .map(|&seat| Player {
+                    seat,
+                    position: None,
+                    name: format!("P{}", seat),
+                    chips: Money::from_cents(500),
+                    chips_after_hand: Money::ZERO,
+                    is_sitting_out: Some(seat) == sitting_out,
+                    is_hero: false,
+                    hole_cards: None,
+                    hand_description: None,
+                })
+                .collect::<Vec<Player>>();
+            let dealt_in = seats.iter().copied().filter(|&seat| Some(seat) != sitting_out).collect::<Vec<u32>>();
+            let button = dealt_in.iter().position(|&seat| seat == dealer_seat).unwrap();
+            let (small_blind, big_blind) = if dealt_in.len() == 2 {
+                (dealt_in[button], dealt_in[(button + 1) % 2])
+            } else {
+                (dealt_in[(button + 1) % dealt_in.len()], dealt_in[(button + 2) % dealt_in.len()])
+            };
+            let pre_actions = vec![
+                PlayerAction { player_name: format!("P{}", small_blind), action: Action::PostSmallBlind(Money::from_cents(2)) },
+                PlayerAction { player_name: format!("P{}", big_blind), action: Action::PostBigBlind(Money::from_cents(5)) },
+            ];
+
+            Hand::assign_positions(&mut players, dealer_seat, &pre_actions);
+            let positions = players.iter().map(|player| player.position.as_ref().map_or("-", |position| position.as_str())).collect::<Vec<&str>>();
+            assert_eq!(positions, expected, "seats {:?}, button {}", seats, dealer_seat);
+        }
+    }
+}
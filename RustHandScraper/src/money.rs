@@ -0,0 +1,124 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+// An amount in hundredths of the hand's unit: cents for cash games, hundredths of a chip for tournaments
+// and play money. Integer arithmetic keeps sums exact however many hands are aggregated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+    // The largest amount a history may state. A hand has at most a few thousand amounts, so sums of them
+    // stay far inside i64 and the operators below can't overflow on anything the parser accepted
+    pub const MAX: Money = Money(100_000_000_000_000);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+
+    // Parses "1,250.5", "0.05" or "1500"; more than two decimals can't be represented and is rejected,
+    // as is anything above Money::MAX
+    pub fn parse(amount: &str) -> Option<Self> {
+        let amount = amount.replace(",", "");
+        let (whole, fraction) = amount.split_once('.').unwrap_or((&amount, ""));
+        if whole.is_empty() || !whole.chars().all(|c| c.is_ascii_digit()) || fraction.len() > 2 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let whole = whole.parse::<i64>().ok()?;
+        let fraction = format!("{:0<2}", fraction).parse::<i64>().ok()?;
+        whole.checked_mul(100)?.checked_add(fraction).map(Money).filter(|amount| *amount <= Money::MAX)
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+
+    // The amount in another currency at `rate` units of it per unit of this one, to the nearest cent
+    pub fn convert(self, rate: f32) -> Money {
+        Money((self.0 as f64 * rate as f64).round() as i64)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / 100.0
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        self.checked_add(other).expect("money overflow")
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        self.checked_sub(other).expect("money overflow")
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, |total, amount| total + amount)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, |total, amount| total + *amount)
+    }
+}
+
+// Prints like the history does ("5", "0.5", "1.37"), or always with cents when a precision is given ("{:.2}")
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let whole = self.0.abs() / 100;
+        let fraction = self.0.abs() % 100;
+        if f.precision().is_some() {
+            write!(f, "{}{}.{:02}", sign, whole, fraction)
+        } else if fraction == 0 {
+            write!(f, "{}{}", sign, whole)
+        } else if fraction % 10 == 0 {
+            write!(f, "{}{}.{}", sign, whole, fraction / 10)
+        } else {
+            write!(f, "{}{}.{:02}", sign, whole, fraction)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_amounts_that_could_overflow_a_sum() {
+        assert_eq!(Money::parse("1,000,000,000,000"), Some(Money::MAX));
+        assert_eq!(Money::parse("1,000,000,000,000.01"), None);
+        assert_eq!(Money::parse("92233720368547758.07"), None);
+        assert_eq!(std::iter::repeat_n(Money::MAX, 10_000).sum::<Money>(), Money::from_cents(Money::MAX.cents() * 10_000));
+    }
+}
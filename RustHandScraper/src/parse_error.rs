@@ -0,0 +1,96 @@
+use std::fmt;
+
+use crate::Street;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandParseErrorKind {
+    MissingHeader,
+    MissingTableInfo,
+    MissingSection(&'static str),
+    MissingHeroCards,
+    MissingBoard,
+    MissingPot,
+    InvalidNumber,
+    UnknownAction,
+    UnseatedPlayer,
+    UnsupportedGame(String),
+    DuplicateCard(String),
+    UnknownTimeZone(String),
+    UnreadableFile(String),
+}
+
+impl HandParseErrorKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            HandParseErrorKind::MissingHeader => "could not parse hand header",
+            HandParseErrorKind::MissingTableInfo => "could not parse table line",
+            HandParseErrorKind::MissingSection(_) => "missing section",
+            HandParseErrorKind::MissingHeroCards => "could not parse hero cards",
+            HandParseErrorKind::MissingBoard => "could not parse board",
+            HandParseErrorKind::MissingPot => "could not parse pot and rake",
+            HandParseErrorKind::InvalidNumber => "invalid number",
+            HandParseErrorKind::UnknownAction => "could not parse action",
+            HandParseErrorKind::UnseatedPlayer => "action by a player who is not seated",
+            HandParseErrorKind::UnsupportedGame(_) => "unsupported game",
+            HandParseErrorKind::DuplicateCard(_) => "card dealt twice",
+            HandParseErrorKind::UnknownTimeZone(_) => "unknown time zone",
+            HandParseErrorKind::UnreadableFile(_) => "could not read hand history file",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HandParseError {
+    pub kind: HandParseErrorKind,
+    pub hand_id: Option<String>,
+    pub street: Option<Street>,
+    pub line_number: Option<usize>, // 1-based, relative to the start of the hand
+    pub line: String,
+}
+
+impl HandParseError {
+    pub fn new(kind: HandParseErrorKind, line: &str) -> Self {
+        HandParseError {
+            kind,
+            hand_id: None,
+            street: None,
+            line_number: None,
+            line: line.trim_end().to_string(),
+        }
+    }
+
+    pub fn in_hand(mut self, hand_id: &str) -> Self {
+        if self.hand_id.is_none() {
+            self.hand_id = Some(hand_id.to_string());
+        }
+        self
+    }
+
+    pub fn at(mut self, street: Street, line_number: usize) -> Self {
+        self.street = Some(street);
+        self.line_number = Some(line_number);
+        self
+    }
+}
+
+impl fmt::Display for HandParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hand #{}", self.hand_id.as_deref().unwrap_or("?"))?;
+        if let Some(street) = &self.street {
+            write!(f, " [{}]", street.as_str())?;
+        }
+        if let Some(line_number) = self.line_number {
+            write!(f, " line {}", line_number)?;
+        }
+        match &self.kind {
+            HandParseErrorKind::MissingSection(section) => write!(f, ": {} {}", self.kind.as_str(), section)?,
+            HandParseErrorKind::UnsupportedGame(game) => write!(f, ": {} {}", self.kind.as_str(), game)?,
+            HandParseErrorKind::UnknownTimeZone(zone) => write!(f, ": {} {}", self.kind.as_str(), zone)?,
+            HandParseErrorKind::UnreadableFile(error) => write!(f, ": {} ({})", self.kind.as_str(), error)?,
+            kind => write!(f, ": {}", kind.as_str())?,
+        }
+        write!(f, ": {}", self.line)
+    }
+}
+
+impl std::error::Error for HandParseError {}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::Hand;
+
+// A break this long at a table ends the session
+const SESSION_BREAK: Duration = Duration::minutes(30);
+
+// A run of hands at one logical table, a Zoom pool counting as one table however often it moves us
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub logical_table: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub hand_ids: Vec<String>,
+}
+
+// Sessions ordered by start; hands at several tables at once make overlapping sessions
+pub fn group_sessions(hands: &[Hand]) -> Vec<Session> {
+    let mut ordered = hands.iter().collect::<Vec<&Hand>>();
+    ordered.sort_by_key(|hand| hand.timestamp);
+
+    let mut sessions: Vec<Session> = Vec::new();
+    for hand in ordered {
+        let logical_table = hand.logical_table();
+        let open_session = sessions.iter_mut().rev()
+            .find(|session| session.logical_table == logical_table)
+            .filter(|session| hand.timestamp - session.end <= SESSION_BREAK);
+        match open_session {
+            Some(session) => {
+                session.end = hand.timestamp;
+                session.hand_ids.push(hand.id.clone());
+            },
+            None => sessions.push(Session {
+                logical_table: logical_table.to_string(),
+                start: hand.timestamp,
+                end: hand.timestamp,
+                hand_ids: vec![hand.id.clone()],
+            }),
+        }
+    }
+    sessions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseMode;
+
+    // A hand hero folds in the small blind; Zoom hands put "Zoom " before "Hand #"
+    fn hand(id: u32, zoom: bool, table: &str, time: &str) -> Hand {
+        let hand_str = format!("PokerStars {}Hand #{}:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 {} CET
+Table '{}' 6-max Seat #1 is the button
+Seat 1: Hero ($5 in chips)
+Seat 2: Bob ($5 in chips)
+Hero: posts small blind $0.02
+Bob: posts big blind $0.05
+*** HOLE CARDS ***
+Dealt to Hero [Ah Kd]
+Hero: folds
+Uncalled bet ($0.03) returned to Bob
+Bob collected $0.04 from pot
+*** SUMMARY ***
+Total pot $0.04 | Rake $0", if zoom { "Zoom " } else { "" }, id, time, table);
+        Hand::from_str(&hand_str, ParseMode::Strict).unwrap()
+    }
+
+    #[test]
+    fn groups_a_zoom_pool_as_one_table_until_a_break() {
+        let hands = [
+            hand(5, true, "Aarhus II", "14:51:00"),
+            hand(1, true, "Aarhus II", "14:00:00"),
+            hand(3, false, "Aarhus II", "14:02:00"),
+            hand(2, true, "Bellatrix III", "14:01:00"),
+            hand(4, true, "Cygnus", "14:20:00"),
+        ];
+        let sessions = group_sessions(&hands);
+
+        let hand_ids = sessions.iter().map(|session| session.hand_ids.join(",")).collect::<Vec<String>>();
+        assert_eq!(hand_ids, ["1,2,4", "3", "5"]);
+        // The Zoom pool is one logical table whatever table each hand was dealt at, and no regular table
+        assert_eq!(sessions[0].logical_table, sessions[2].logical_table);
+        assert_eq!(sessions[1].logical_table, "Aarhus II");
+        assert_eq!((sessions[0].start, sessions[0].end), (hands[1].timestamp, hands[4].timestamp));
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::amount::{self, parse_amount};
+use crate::card::{Card, parse_cards};
+use crate::money::Money;
+use crate::Street;
+
+// What the summary says happened to a seat, e.g. "folded before Flop (didn't bet)" or
+// "showed [Qs Qh] and won ($1.08) with a pair of Queens"
+#[derive(Debug, Clone, PartialEq)]
+pub enum SeatOutcome {
+    Folded { street: Street, didnt_bet: bool },
+    Showed { cards: Vec<Card>, won: Money, description: Option<String> },
+    Mucked { cards: Option<Vec<Card>> },
+    Collected(Money),
+    SittingOut,
+    Other(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct SeatSummary {
+    pub seat: u32,
+    pub name: String,
+    pub outcome: SeatOutcome,
+}
+
+impl SeatSummary {
+    pub fn to_json(&self) -> String {
+        let cards_json = |cards: &[Card]| cards.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(",");
+        let outcome_json = match &self.outcome {
+            SeatOutcome::Folded { street, didnt_bet } => format!("{{\"type\":\"Folded\",\"street\":\"{}\",\"didnt_bet\":{}}}", street.as_str(), didnt_bet),
+            SeatOutcome::Showed { cards, won, description } => format!(
+                "{{\"type\":\"Showed\",\"cards\":[{}],\"won\":{},\"desc\":{}}}",
+                cards_json(cards),
+                won,
+                description.as_ref().map_or("null".to_string(), |description| format!("\"{}\"", description.replace("\"", "\\\"")))
+            ),
+            SeatOutcome::Mucked { cards } => format!("{{\"type\":\"Mucked\",\"cards\":{}}}", cards.as_ref().map_or("null".to_string(), |cards| format!("[{}]", cards_json(cards)))),
+            SeatOutcome::Collected(amount) => format!("{{\"type\":\"Collected\",\"amount\":{}}}", amount),
+            SeatOutcome::SittingOut => "{\"type\":\"SittingOut\"}".to_string(),
+            SeatOutcome::Other(text) => format!("{{\"type\":\"Other\",\"text\":\"{}\"}}", text.replace("\"", "\\\"")),
+        };
+        format!("{{\"seat\":{},\"name\":\"{}\",\"outcome\":{}}}", self.seat, self.name.replace("\"", "\\\""), outcome_json)
+    }
+
+    // Parses the seat lines after "*** SUMMARY ***". `seats` are the seated players; their names are matched
+    // as given rather than guessed from the line, since names can contain brackets and spaces
+    pub fn parse_all(summary_str: &str, seats: &[(u32, String)]) -> Vec<SeatSummary> {
+        summary_str.lines()
+            .filter_map(|line| seats.iter().find_map(|(seat, name)| {
+                let rest = line.strip_prefix(&format!("Seat {}: {} ", seat, name))?;
+                Some(SeatSummary { seat: *seat, name: name.clone(), outcome: SeatOutcome::from_str(rest) })
+            }))
+            .collect()
+    }
+}
+
+impl SeatOutcome {
+    fn from_str(rest: &str) -> Self {
+        static FOLDED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^folded (?:before Flop|on the (Flop|Turn|River))( \(didn't bet\))?").unwrap());
+        static SHOWED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^showed \[((?:[2-9TJQKA][cdhs] ?)+)\](.*)$").unwrap());
+        static MUCKED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^mucked(?: \[((?:[2-9TJQKA][cdhs] ?)+)\])?").unwrap());
+        static COLLECTED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"^collected \({amount}\)")).unwrap());
+        // Run-it-twice lines win or lose once per board: "and won ($1) with ..., and lost with ..."
+        static WON_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(&amount::expand(r"won \({amount}\)")).unwrap());
+        static DESCRIPTION_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r" with (.+?)(?:, and |$)").unwrap());
+
+        // Position tags come before the outcome: "(button) (small blind) folded before Flop"
+        let mut rest = rest.trim_end();
+        while let Some(stripped) = ["(button) ", "(small blind) ", "(big blind) "].iter().find_map(|tag| rest.strip_prefix(tag)) {
+            rest = stripped;
+        }
+
+        if let Some(caps) = FOLDED_RE.captures(rest) {
+            let street = match caps.get(1).map(|street| street.as_str()) {
+                Some("Flop") => Street::Flop,
+                Some("Turn") => Street::Turn,
+                Some("River") => Street::River,
+                _ => Street::Preflop,
+            };
+            return SeatOutcome::Folded { street, didnt_bet: caps.get(2).is_some() };
+        }
+        if let Some(caps) = SHOWED_RE.captures(rest)
+            && let Some(cards) = parse_cards(caps.get(1).unwrap().as_str()) {
+            let results = caps.get(2).unwrap().as_str();
+            return SeatOutcome::Showed {
+                cards,
+                won: WON_RE.captures_iter(results).filter_map(|caps| parse_amount(&caps, 1)).sum(),
+                description: DESCRIPTION_RE.captures(results).map(|caps| caps.get(1).unwrap().as_str().to_string()),
+            };
+        }
+        if let Some(caps) = MUCKED_RE.captures(rest) {
+            return SeatOutcome::Mucked { cards: caps.get(1).and_then(|cards| parse_cards(cards.as_str())) };
+        }
+        if let Some(amount) = COLLECTED_RE.captures(rest).and_then(|caps| parse_amount(&caps, 1)) {
+            return SeatOutcome::Collected(amount);
+        }
+        if rest == "is sitting out" {
+            return SeatOutcome::SittingOut;
+        }
+        SeatOutcome::Other(rest.to_string())
+    }
+}
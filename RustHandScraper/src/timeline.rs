@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::money::Money;
+use crate::{Action, Hand, Street};
+
+// One action of the hand in the order it happened, with the pot and the player's stack just before it.
+// `seat`, `stack_before` and `effective_stack` are None for table events from players who aren't seated;
+// `spr` is None while the pot is empty and `bet_fraction` is only set for bets and raises
+#[derive(Debug, Clone)]
+pub struct TimelineEntry {
+    pub street: Street,
+    pub sequence: u32,
+    pub seat: Option<u32>,
+    pub player: String,
+    pub action: Action,
+    pub pot_before: Money,
+    pub stack_before: Option<Money>,
+    pub to_call: Money,
+    pub effective_stack: Option<Money>, // the smaller of the player's stack and the biggest stack still against them
+    pub spr: Option<f32>,
+    pub bet_fraction: Option<f32>, // chips the bet or raise adds, as a fraction of the pot before it
+}
+
+impl TimelineEntry {
+    // The street is sent as its number (pre=0 ... showdown=5) like the Actions table stores it
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"street\":{},\"sequence\":{},\"seat\":{},\"player\":\"{}\",\"action\":{},\"pot_before\":{},\"stack_before\":{},\"to_call\":{},\"effective_stack\":{},\"spr\":{},\"bet_fraction\":{}}}",
+            self.street as u32,
+            self.sequence,
+            self.seat.map_or("null".to_string(), |seat| seat.to_string()),
+            self.player.replace("\"", "\\\""),
+            self.action.to_json(),
+            self.pot_before,
+            self.stack_before.map_or("null".to_string(), |stack| stack.to_string()),
+            self.to_call,
+            self.effective_stack.map_or("null".to_string(), |stack| stack.to_string()),
+            self.spr.map_or("null".to_string(), |spr| spr.to_string()),
+            self.bet_fraction.map_or("null".to_string(), |fraction| fraction.to_string())
+        )
+    }
+}
+
+// Replays the hand street by street, moving chips between the stacks and the pot as every action happens
+pub fn build_timeline(hand: &Hand) -> Vec<TimelineEntry> {
+    let small_blind = hand.game_info.small_blind;
+    let mut stacks: HashMap<&str, Money> = hand.players.iter().map(|player| (player.name.as_str(), player.chips)).collect();
+    // What each player has put in on the current street, which is what a raise "to" is measured against
+    let mut street_bets: HashMap<&str, Money> = HashMap::new();
+    // Players who folded or sat this hand out no longer count towards anyone's effective stack
+    let mut out_of_hand: HashSet<&str> = hand.players.iter().filter(|player| player.is_sitting_out).map(|player| player.name.as_str()).collect();
+    let mut pot = Money::ZERO;
+    let mut timeline = Vec::new();
+
+    let streets = [
+        (Street::Pre, &hand.pre_actions),
+        (Street::Preflop, &hand.preflop_actions),
+        (Street::Flop, &hand.flop_actions),
+        (Street::Turn, &hand.turn_actions),
+        (Street::River, &hand.river_actions),
+        (Street::ShowDown, &hand.show_down_actions),
+    ];
+    for (street, actions) in streets {
+        // Blinds posted before the hole cards are the first preflop bets
+        if !matches!(street, Street::Pre | Street::Preflop) {
+            street_bets.clear();
+        }
+
+        for action in actions.iter() {
+            let name = action.player_name.as_str();
+            let stack_before = stacks.get(name).copied();
+            let own_bet = street_bets.get(name).copied().unwrap_or_default();
+            let biggest_bet = street_bets.values().copied().max().unwrap_or_default();
+            let to_call = (biggest_bet - own_bet).min(stack_before.unwrap_or_default()).max(Money::ZERO);
+            let biggest_opponent_stack = stacks.iter()
+                .filter(|(other, _)| **other != name && !out_of_hand.contains(*other))
+                .map(|(_, stack)| *stack)
+                .max()
+                .unwrap_or_default();
+            let effective_stack = stack_before.map(|stack| stack.min(biggest_opponent_stack));
+            let spr = effective_stack.filter(|_| pot > Money::ZERO).map(|stack| stack.to_f32() / pot.to_f32());
+            let added = match action.action {
+                Action::Bet(amount) | Action::BetAndAllIn(amount) => Some(amount),
+                Action::Raise(_, to) | Action::RaiseAndAllIn(_, to) => Some(to - own_bet),
+                _ => None,
+            };
+            let bet_fraction = added.filter(|_| pot > Money::ZERO).map(|added| added.to_f32() / pot.to_f32());
+
+            timeline.push(TimelineEntry {
+                street,
+                sequence: timeline.len() as u32,
+                seat: hand.players.iter().find(|player| player.name == name).map(|player| player.seat),
+                player: action.player_name.clone(),
+                action: action.action.clone(),
+                pot_before: pot,
+                stack_before,
+                to_call,
+                effective_stack,
+                spr,
+                bet_fraction,
+            });
+            if matches!(action.action, Action::Fold | Action::SitsOut) {
+                out_of_hand.insert(name);
+            }
+
+            let bet = street_bets.entry(name).or_default();
+            let (into_pot, out_of_pot) = match action.action {
+                Action::PostSmallBlind(amount)
+                | Action::PostBigBlind(amount)
+                | Action::PostStraddle(amount)
+                | Action::Bet(amount)
+                | Action::BetAndAllIn(amount)
+                | Action::Call(amount)
+                | Action::CallAndAllIn(amount) => {
+                    *bet += amount;
+                    (amount, Money::ZERO)
+                },
+                // The small blind part is dead money, like an ante
+                Action::PostSmallAndBigBlind(amount) => {
+                    *bet += amount - small_blind;
+                    (amount, Money::ZERO)
+                },
+                Action::PostAnte(amount) => (amount, Money::ZERO),
+                Action::Raise(_, to) | Action::RaiseAndAllIn(_, to) => {
+                    let raised = to - *bet;
+                    *bet = to;
+                    (raised, Money::ZERO)
+                },
+                Action::UncalledBet(amount) => {
+                    *bet -= amount;
+                    (Money::ZERO, amount)
+                },
+                // A cash-out is paid from the pot like a collection
+                Action::Collected(amount)
+                | Action::CollectedFromMainPot(amount)
+                | Action::CollectedFromSidePot(amount, _)
+                | Action::CashedOut(amount, _) => (Money::ZERO, amount),
+                // Chips added at the table go straight onto the stack
+                Action::AddsChips(amount) => {
+                    if let Some(stack) = stacks.get_mut(name) {
+                        *stack += amount;
+                    }
+                    (Money::ZERO, Money::ZERO)
+                },
+                _ => (Money::ZERO, Money::ZERO),
+            };
+            pot = pot + into_pot - out_of_pot;
+            if let Some(stack) = stacks.get_mut(name) {
+                *stack = *stack + out_of_pot - into_pot;
+            }
+        }
+    }
+
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseMode;
+
+    const CASH_OUT_HAND: &str = "PokerStars Hand #250123456795:  Hold'em No Limit ($0.02/$0.05 USD) - 2024/11/03 14:30:00 CET [2024/11/03 8:30:00 ET]
+Table 'Aarhus II' 6-max Seat #1 is the button
+Seat 1: Hero ($5 in chips)
+Seat 2: Bob ($5 in chips)
+Seat 3: Carl ($5 in chips)
+Bob: posts small blind $0.02
+Carl: posts big blind $0.05
+Carl adds $5
+*** HOLE CARDS ***
+Dealt to Hero [Ah Ad]
+Hero: raises $4.95 to $5 and is all-in
+Bob: calls $4.98 and is all-in
+Carl: folds
+*** FLOP *** [2c 3d 4h]
+*** TURN *** [2c 3d 4h] [5s]
+Hero cashed out the hand for $9.50 | Cash Out Fee $0.10
+*** RIVER *** [2c 3d 4h 5s] [Kd]
+*** SHOW DOWN ***
+Hero: shows [Ah Ad] (a pair of Aces)
+Bob: shows [Kc Qc] (a pair of Kings)
+*** SUMMARY ***
+Total pot $10.05 | Rake $0.55
+Board [2c 3d 4h 5s Kd]";
+
+    #[test]
+    fn added_chips_and_cash_outs_move_the_stack() {
+        let hand = Hand::from_str(CASH_OUT_HAND, ParseMode::Strict).unwrap();
+        let timeline = build_timeline(&hand);
+        let entry = |player: &str, action: fn(&Action) -> bool| timeline.iter().find(|entry| entry.player == player && action(&entry.action)).unwrap();
+
+        let fold = entry("Carl", |action| matches!(action, Action::Fold));
+        assert_eq!(fold.stack_before, Some(Money::from_cents(995)));
+
+        let shows = entry("Hero", |action| matches!(action, Action::Shows(..)));
+        assert_eq!(shows.stack_before, Some(Money::from_cents(950)));
+        assert_eq!(shows.pot_before, Money::from_cents(55));
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+
+enum DaylightSaving {
+    None,
+    UnitedStates, // second Sunday of March to the first Sunday of November, at 2:00 local time
+    EuropeanUnion, // last Sunday of March to the last Sunday of October, at 1:00 UTC
+}
+
+// Zone tokens PokerStars writes after the local time, with their standard offset in minutes.
+// The token names the zone, not the season: "ET" and "CET" are used all year round
+fn zone_rules(zone: &str) -> Option<(i64, DaylightSaving)> {
+    match zone {
+        "UTC" | "GMT" => Some((0, DaylightSaving::None)),
+        "WET" => Some((0, DaylightSaving::EuropeanUnion)),
+        "CET" => Some((60, DaylightSaving::EuropeanUnion)),
+        "EET" => Some((120, DaylightSaving::EuropeanUnion)),
+        "MSK" => Some((180, DaylightSaving::None)),
+        "BRT" => Some((-180, DaylightSaving::None)),
+        "AT" => Some((-240, DaylightSaving::UnitedStates)),
+        "ET" => Some((-300, DaylightSaving::UnitedStates)),
+        "CT" => Some((-360, DaylightSaving::UnitedStates)),
+        "MT" => Some((-420, DaylightSaving::UnitedStates)),
+        "PT" => Some((-480, DaylightSaving::UnitedStates)),
+        "AKT" => Some((-540, DaylightSaving::UnitedStates)),
+        "HT" => Some((-600, DaylightSaving::None)),
+        _ => None,
+    }
+}
+
+// Converts a local hand time to UTC, or None for a zone we don't know. In the hour that repeats when
+// clocks go back the time is read as standard time, the later of the two
+pub fn to_utc(local: NaiveDateTime, zone: &str) -> Option<DateTime<Utc>> {
+    let (standard_offset, daylight_saving) = zone_rules(zone)?;
+    let year = local.year();
+    let at = |date: NaiveDate, hour: u32| date.and_hms_opt(hour, 0, 0);
+    let (start, end) = match daylight_saving {
+        DaylightSaving::None => (None, None),
+        DaylightSaving::UnitedStates => (
+            at(NaiveDate::from_weekday_of_month_opt(year, 3, Weekday::Sun, 2)?, 2),
+            at(NaiveDate::from_weekday_of_month_opt(year, 11, Weekday::Sun, 1)?, 1),
+        ),
+        DaylightSaving::EuropeanUnion => {
+            // 1:00 UTC in local standard time, e.g. 2:00 for CET
+            let switch_hour = (60 + standard_offset) / 60;
+            (at(last_sunday(year, 3)?, switch_hour as u32), at(last_sunday(year, 10)?, switch_hour as u32))
+        },
+    };
+    let is_daylight_saving = matches!((start, end), (Some(start), Some(end)) if local >= start && local < end);
+    let offset = standard_offset + if is_daylight_saving { 60 } else { 0 };
+    Some(Utc.from_utc_datetime(&(local - Duration::minutes(offset))))
+}
+
+fn last_sunday(year: i32, month: u32) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, 5).or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_local_times_on_both_sides_of_the_daylight_saving_switches() {
+        // Local time, zone and the expected UTC time
+        let cases = [
+            // US clocks go forward at 2:00 on 10 March 2024 and back at 2:00 on 3 November 2024
+            ("2024-03-10 01:59:00", "ET", "2024-03-10 06:59:00"),
+            ("2024-03-10 03:00:00", "ET", "2024-03-10 07:00:00"),
+            ("2024-11-03 00:59:00", "ET", "2024-11-03 04:59:00"),
+            ("2024-11-03 01:30:00", "ET", "2024-11-03 06:30:00"), // the repeated hour is read as standard time
+            ("2024-11-03 08:22:10", "ET", "2024-11-03 13:22:10"),
+            ("2024-07-01 12:00:00", "PT", "2024-07-01 19:00:00"),
+            // EU clocks go forward at 1:00 UTC on 31 March 2024 and back at 1:00 UTC on 27 October 2024
+            ("2024-03-31 01:59:00", "CET", "2024-03-31 00:59:00"),
+            ("2024-03-31 03:00:00", "CET", "2024-03-31 01:00:00"),
+            ("2024-10-27 01:59:00", "CET", "2024-10-26 23:59:00"),
+            ("2024-10-27 02:30:00", "CET", "2024-10-27 01:30:00"),
+            ("2024-11-03 14:22:10", "CET", "2024-11-03 13:22:10"),
+            ("2024-07-01 12:00:00", "WET", "2024-07-01 11:00:00"),
+            // Zones without daylight saving
+            ("2024-07-01 12:00:00", "BRT", "2024-07-01 15:00:00"),
+            ("2024-07-01 12:00:00", "MSK", "2024-07-01 09:00:00"),
+        ];
+        for (local, zone, expected) in cases {
+            let local = NaiveDateTime::parse_from_str(local, "%Y-%m-%d %H:%M:%S").unwrap();
+            let utc = to_utc(local, zone).unwrap();
+            assert_eq!(utc.format("%Y-%m-%d %H:%M:%S").to_string(), expected, "{} {}", local, zone);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_zones() {
+        let local = NaiveDateTime::parse_from_str("2024-07-01 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        assert_eq!(to_utc(local, "XYZ"), None);
+    }
+}
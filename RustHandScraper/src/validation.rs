@@ -0,0 +1,51 @@
+use std::fmt;
+
+use crate::money::Money;
+
+// A way in which a parsed hand doesn't add up; any of these points at a parser bug or a history we misread
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    Contributions { contributed: Money, total_pot: Money },
+    UncalledBet { player: String, amount: Money, expected: Money },
+    PotLimit { player: String, street: String, to: Money, pot_limit: Money },
+    Pots { pots: Money, total_pot: Money },
+    Collected { collected: Money, expected: Money },
+    Stack { player: String, chips_after_hand: Money, next_hand_id: String, next_hand_chips: Money },
+    SummaryFold { player: String, street: String },
+    SummaryWinnings { player: String, summary: Money, collected: Money },
+    SummaryCards { player: String, cards: String },
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::Contributions { contributed, total_pot } => {
+                write!(f, "players put {} into the pot but the total pot is {}", contributed, total_pot)
+            },
+            ValidationIssue::UncalledBet { player, amount, expected } => {
+                write!(f, "uncalled bet of {} returned to {} should be {}", amount, player, expected)
+            },
+            ValidationIssue::PotLimit { player, street, to, pot_limit } => {
+                write!(f, "{} bets or raises to {} on {} but the pot limit is {}", player, to, street, pot_limit)
+            },
+            ValidationIssue::Pots { pots, total_pot } => {
+                write!(f, "main and side pots add up to {} but the total pot is {}", pots, total_pot)
+            },
+            ValidationIssue::Collected { collected, expected } => {
+                write!(f, "players collected {} but the pot less rake is {}", collected, expected)
+            },
+            ValidationIssue::Stack { player, chips_after_hand, next_hand_id, next_hand_chips } => {
+                write!(f, "{} ends the hand with {} but starts hand #{} with {}", player, chips_after_hand, next_hand_id, next_hand_chips)
+            },
+            ValidationIssue::SummaryFold { player, street } => {
+                write!(f, "the summary has {} folding on {} but no fold is recorded there", player, street)
+            },
+            ValidationIssue::SummaryWinnings { player, summary, collected } => {
+                write!(f, "the summary has {} winning {} but the actions collect {}", player, summary, collected)
+            },
+            ValidationIssue::SummaryCards { player, cards } => {
+                write!(f, "the summary shows [{}] for {} but the hand shows other cards", cards, player)
+            },
+        }
+    }
+}
@@ -0,0 +1,11 @@
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'PostSmallAndBigBlind';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'Chat';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'Returned';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'IsSittingOut';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'WaitsForButton';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'AddsChips';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'PostAnte';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'PostStraddle';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'FinishedTournament';--> statement-breakpoint
+ALTER TYPE "public"."action_name_enum" ADD VALUE 'WonBounty';--> statement-breakpoint
+ALTER TYPE "public"."position_enum" ADD VALUE 'UTG3' BEFORE 'LJ';
\ No newline at end of file
@@ -29,6 +29,16 @@ export const ActionNames = {
   Connected: "Connected" as ActionName,
   CollectedFromSidePot: "CollectedFromSidePot" as ActionName,
   CollectedFromMainPot: "CollectedFromMainPot" as ActionName,
+  PostSmallAndBigBlind: "PostSmallAndBigBlind" as ActionName,
+  Chat: "Chat" as ActionName,
+  Returned: "Returned" as ActionName,
+  IsSittingOut: "IsSittingOut" as ActionName,
+  WaitsForButton: "WaitsForButton" as ActionName,
+  AddsChips: "AddsChips" as ActionName,
+  PostAnte: "PostAnte" as ActionName,
+  PostStraddle: "PostStraddle" as ActionName,
+  FinishedTournament: "FinishedTournament" as ActionName,
+  WonBounty: "WonBounty" as ActionName,
 };
 
 export function isValidActionName(text: string): text is ActionName {
@@ -11,6 +11,7 @@ export const Positions = {
   UTG: "UTG" as Position,
   UTG1: "UTG1" as Position,
   UTG2: "UTG2" as Position,
+  UTG3: "UTG3" as Position,
   LJ: "LJ" as Position,
   HJ: "HJ" as Position,
   CO: "CO" as Position,
@@ -19,6 +19,7 @@ export const positionEnum = pgEnum(`position_enum`, [
   "UTG",
   "UTG1",
   "UTG2",
+  "UTG3",
   "LJ",
   "HJ",
   "CO",
@@ -49,6 +50,16 @@ export const actionNameEnum = pgEnum(`action_name_enum`, [
   "Connected",
   "CollectedFromSidePot",
   "CollectedFromMainPot",
+  "PostSmallAndBigBlind",
+  "Chat",
+  "Returned",
+  "IsSittingOut",
+  "WaitsForButton",
+  "AddsChips",
+  "PostAnte",
+  "PostStraddle",
+  "FinishedTournament",
+  "WonBounty",
 ]);
 
 export const chartTypeEnum = pgEnum(`chart_type_enum`, [